@@ -1,13 +1,14 @@
 #![cfg_attr(feature = "unstable", feature(extend_one))]
 
-use quote::quote;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 use syn::{
-    AngleBracketedGenericArguments, GenericArgument, ItemStruct, Path, PathArguments,
-    PathSegment, Type, TypePath,
+    AngleBracketedGenericArguments, Attribute, Data, Expr, Field, GenericArgument, Generics, Ident,
+    ItemStruct, LitStr, Path, PathArguments, PathSegment, Token, Type, TypePath, Visibility,
 };
 
 pub fn get_segment_from_type(type_: &Type) -> &PathSegment {
-    get_segment(&get_path(type_))
+    get_segment(get_path(type_))
 }
 
 pub fn get_segment(path: &Path) -> &PathSegment {
@@ -22,6 +23,7 @@ pub fn get_path(elem: &Type) -> &Path {
     path
 }
 
+#[allow(clippy::result_unit_err)]
 pub fn type_from_args(arguments: &PathArguments) -> Result<&Type, ()> {
     let PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) = arguments
     else {
@@ -33,6 +35,258 @@ pub fn type_from_args(arguments: &PathArguments) -> Result<&Type, ()> {
     Ok(arg)
 }
 
+/// Тип, к которому разыменовывается владеющий тип стандартной библиотеки:
+/// `String` -> `str`, `Vec<T>` -> `[T]`, `Box<T>` -> `T` и т.д.
+pub fn deref_target(type_: &Type) -> Option<Type> {
+    let Type::Path(TypePath { path, qself: None }) = type_ else {
+        return None;
+    };
+    let PathSegment { ident, arguments } = path.segments.last()?;
+    let target = match ident.to_string().as_str() {
+        "String" => quote!(str),
+        "PathBuf" => quote!(::std::path::Path),
+        "OsString" => quote!(::std::ffi::OsStr),
+        "CString" => quote!(::std::ffi::CStr),
+        "Vec" => {
+            let inner = type_from_args(arguments).ok()?;
+            quote!([#inner])
+        }
+        "Box" | "Rc" | "Arc" => {
+            let inner = type_from_args(arguments).ok()?;
+            quote!(#inner)
+        }
+        _ => return None,
+    };
+    syn::parse2(target).ok()
+}
+
+/// Упоминаются ли в типе параметры типа или константные параметры из `generics`.
+///
+/// `PhantomData<T>` не считается упоминанием `T`.
+pub fn uses_type_params(type_: &Type, generics: &Generics) -> bool {
+    fn contains(tokens: TokenStream, generics: &Generics) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => {
+                generics.type_params().any(|param| param.ident == ident)
+                    || generics.const_params().any(|param| param.ident == ident)
+            }
+            TokenTree::Group(group) => contains(group.stream(), generics),
+            _ => false,
+        })
+    }
+    if let Type::Path(TypePath { path, qself: None }) = type_ {
+        if path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "PhantomData")
+        {
+            return false;
+        }
+    }
+    contains(type_.to_token_stream(), generics)
+}
+
+/// Стиль написания имён.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+}
+
+impl Case {
+    /// Стиль по его написанию: `"snake_case"`, `"kebab-case"` и т.д.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "PascalCase" => Self::Pascal,
+            "camelCase" => Self::Camel,
+            "snake_case" => Self::Snake,
+            "SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+            "kebab-case" => Self::Kebab,
+            "lowercase" => Self::Lower,
+            "UPPERCASE" => Self::Upper,
+            _ => return None,
+        })
+    }
+
+    /// Слова имени в `snake_case` или `PascalCase`: `HttpServer2` -> `["Http", "Server2"]`.
+    fn words(name: &str) -> Vec<String> {
+        let mut words = vec![];
+        for part in name.split('_').filter(|part| !part.is_empty()) {
+            let chars: Vec<char> = part.chars().collect();
+            let mut word = String::new();
+            for (i, &ch) in chars.iter().enumerate() {
+                let is_boundary = ch.is_uppercase()
+                    && i > 0
+                    && (!chars[i - 1].is_uppercase()
+                        || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+                if is_boundary && !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+                word.push(ch);
+            }
+            words.push(word);
+        }
+        words
+    }
+
+    /// Имя `name`, записанное в этом стиле.
+    pub fn convert(self, name: &str) -> String {
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect()
+            })
+        };
+        let words = Self::words(name);
+        match self {
+            Self::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Self::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| match i {
+                    0 => word.to_lowercase(),
+                    _ => capitalize(word),
+                })
+                .collect(),
+            Self::Snake => words.join("_").to_lowercase(),
+            Self::ScreamingSnake => words.join("_").to_uppercase(),
+            Self::Kebab => words.join("-").to_lowercase(),
+            Self::Lower => words.concat().to_lowercase(),
+            Self::Upper => words.concat().to_uppercase(),
+        }
+    }
+}
+
+/// Общие настройки поля из атрибута `#[std_reset(...)]`.
+///
+/// Атрибут понимают все derive-макросы: каждый использует подходящие ему настройки,
+/// а остальные только проверяет.
+#[derive(Default)]
+pub struct FieldOptions {
+    /// `skip` - макрос не обрабатывает поле.
+    pub skip: bool,
+    /// `rename = "..."` - имя поля в генерируемых методах и строках.
+    pub rename: Option<LitStr>,
+    /// `vis = "..."` - видимость генерируемых методов.
+    pub vis: Option<Visibility>,
+    /// `into` - методы принимают `impl Into<T>`.
+    pub is_into: bool,
+    /// `default = expr` - значение поля по умолчанию.
+    pub default: Option<Expr>,
+}
+
+impl FieldOptions {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("std_reset"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("vis") {
+                    options.vis = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                } else if meta.path.is_ident("into") {
+                    options.is_into = true;
+                } else if meta.path.is_ident("default") {
+                    options.default = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `skip`, `rename`, `vis`, `into` or `default`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+
+    /// Разбирает `#[std_reset(...)]` и отклоняет настройки из `unsupported`,
+    /// которые противоречат коду макроса `derive`.
+    ///
+    /// Настройки, которые к макросу не относятся (например, `vis` для макроса без методов),
+    /// не отклоняются: атрибут общий для всех derive-макросов структуры.
+    pub fn parse_checked(
+        attrs: &[Attribute],
+        derive: &str,
+        unsupported: &[&str],
+    ) -> syn::Result<Self> {
+        let options = Self::parse(attrs)?;
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("std_reset"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if unsupported.iter().any(|key| meta.path.is_ident(key)) {
+                    let key = meta.path.to_token_stream();
+                    return Err(meta.error(format!("`{key}` is not supported by `{derive}`")));
+                }
+                if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+
+    /// Проверяет `#[std_reset(...)]` всех полей и вариантов.
+    pub fn validate(data: &Data, derive: &str, unsupported: &[&str]) -> syn::Result<()> {
+        match data {
+            Data::Struct(data) => Self::validate_fields(&data.fields, derive, unsupported),
+            Data::Enum(data) => data.variants.iter().try_for_each(|variant| {
+                Self::parse_checked(&variant.attrs, derive, unsupported)?;
+                Self::validate_fields(&variant.fields, derive, unsupported)
+            }),
+            Data::Union(data) => Self::validate_fields(&data.fields.named, derive, unsupported),
+        }
+    }
+
+    /// Проверяет `#[std_reset(...)]` полей.
+    pub fn validate_fields<'a>(
+        fields: impl IntoIterator<Item = &'a Field>,
+        derive: &str,
+        unsupported: &[&str],
+    ) -> syn::Result<()> {
+        fields
+            .into_iter()
+            .try_for_each(|field| Self::parse_checked(&field.attrs, derive, unsupported).map(drop))
+    }
+
+    /// Имя поля с учётом `rename`.
+    pub fn name(&self, name: String) -> String {
+        self.rename.as_ref().map_or(name, LitStr::value)
+    }
+
+    /// Идентификатор метода `<prefix><имя поля>` с учётом `rename`.
+    pub fn method_ident(&self, prefix: &str, name: String) -> syn::Result<Ident> {
+        let name = format!("{prefix}{}", self.name(name));
+        syn::parse_str(&name).map_err(|_| {
+            let message = format!("`{name}` is not a valid method name");
+            match &self.rename {
+                Some(rename) => syn::Error::new_spanned(rename, message),
+                None => syn::Error::new(Span::call_site(), message),
+            }
+        })
+    }
+}
+
 pub fn fast_impl(
     struct_: &ItemStruct,
     methods: proc_macro2::TokenStream,
@@ -49,7 +303,6 @@ pub fn fast_impl(
     }
 }
 
-
 #[cfg(feature = "unstable")]
 pub trait Split {
     fn split(&self, split: &proc_macro2::TokenStream) -> Vec<proc_macro2::TokenStream>;
@@ -98,4 +351,4 @@ impl Split for proc_macro2::TokenStream {
         }
         arr
     }
-}
\ No newline at end of file
+}
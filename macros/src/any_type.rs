@@ -33,7 +33,7 @@ impl Collection<'_> {
             }
         }
     }
-    fn define(type_: &Type) -> Collection {
+    fn define(type_: &Type) -> Collection<'_> {
         match type_ {
             Type::Array(TypeArray { elem, len, .. }) => {
                 let len = len.to_token_stream().to_string().parse::<usize>().unwrap();
@@ -48,7 +48,7 @@ impl Collection<'_> {
                     _ => panic!(),
                 });
 
-                if ident.to_string() == "Vec" {
+                if *ident == "Vec" {
                     let inner_type =
                         type_from_args(arguments).expect("у вектора дожно быть только один тип");
                     return Collection::Vec(inner_type);
@@ -113,13 +113,13 @@ impl Reference<'_> {
         syn::parse2::<Type>(self.get_token()).unwrap()
     }
 
-    fn define(type_: &Type) -> Reference {
+    fn define(type_: &Type) -> Reference<'_> {
         match type_ {
             Type::Path(syn::TypePath { path, .. }) => {
                 let syn::PathSegment {
                     ident, arguments, ..
                 } = get_segment(path);
-                if ident.to_string() == "Rc" {
+                if *ident == "Rc" {
                     let inner_type =
                         type_from_args(arguments).expect("Rc должен иметь один параметр");
                     return Reference::Rc(inner_type);
@@ -133,7 +133,7 @@ impl Reference<'_> {
                 let syn::PathSegment {
                     ident, arguments, ..
                 } = get_segment_from_type(type_);
-                if ident.to_string() == "Rc" {
+                if *ident == "Rc" {
                     let inner_type =
                         type_from_args(arguments).expect("Rc должен иметь один параметр");
                     return Reference::RefRc(inner_type);
@@ -144,7 +144,7 @@ impl Reference<'_> {
         }
     }
 
-    fn get_transfers(&self) -> Vec<Reference> {
+    fn get_transfers(&self) -> Vec<Reference<'_>> {
         let type_ = self.get_inner_type();
         vec![
             Reference::NoneRef(type_),
@@ -211,12 +211,12 @@ pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 fn tmp(type_: &Type) -> Vec<proc_macro2::TokenStream> {
-    let ref_ = Reference::define(&type_);
+    let ref_ = Reference::define(type_);
     let mut vec = vec![];
     for ref_1 in ref_.get_transfers() {
-        let col = Collection::define(&ref_1.get_inner_type());
+        let col = Collection::define(ref_1.get_inner_type());
         for col in col.get_transfers() {
-            let ref_ = Reference::define(&col.get_inner_type());
+            let ref_ = Reference::define(col.get_inner_type());
             for ref_2 in ref_.get_transfers() {
                 let y = col.set_type(&ref_2.get_type()).get_type();
                 vec.push(ref_1.set_type(&y).get_token());
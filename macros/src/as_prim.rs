@@ -0,0 +1,135 @@
+use macro_functions::FieldOptions;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Fields, Ident};
+
+/// Целочисленные типы, допустимые в `#[repr(...)]`.
+const REPRS: [&str; 12] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Целочисленный тип из `#[repr(...)]` перечисления.
+fn repr(input: &DeriveInput) -> syn::Result<Ident> {
+    let mut repr = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                if REPRS.contains(&ident.to_string().as_str()) {
+                    repr = Some(ident.clone());
+                }
+            }
+            // остальные аргументы `repr` (`C`, `align(...)`) не важны для преобразований
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    repr.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &input.ident,
+            format!("expected `#[repr(...)]` with one of: {}", REPRS.join(", ")),
+        )
+    })
+}
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let variants = match data {
+        Data::Enum(DataEnum { variants, .. }) => variants,
+        Data::Struct(DataStruct { struct_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                struct_token,
+                "structs are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+    if let Some(variant) = variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new_spanned(
+            &variant.fields,
+            "only unit variants are supported",
+        ));
+    }
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            generics,
+            "generic enums are not supported",
+        ));
+    }
+    FieldOptions::validate(data, "AsPrim", &["skip"])?;
+    let repr = repr(input)?;
+    let idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+
+    let error = format_ident!("{ident}DiscriminantError");
+    let error_doc = format!(" Неизвестный дискриминант [`{ident}`].");
+    let message = format!("unknown discriminant `{{}}` of `{ident}`");
+
+    Ok(quote! {
+        impl #ident {
+            /// Дискриминант варианта, приведённый к числовому типу `I`.
+            pub fn as_<I: ::std_reset::traits::as_prim::FromPrim>(self) -> I {
+                I::as_from(self as #repr)
+            }
+        }
+
+        impl ::std_reset::traits::of_to::Of<#ident> for #repr {
+            fn of(value: #ident) -> Self {
+                value as #repr
+            }
+        }
+
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #error(pub #repr);
+
+        impl ::std::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(f, #message, self.0)
+            }
+        }
+
+        impl ::std::error::Error for #error {}
+
+        impl ::std::convert::TryFrom<#repr> for #ident {
+            type Error = #error;
+
+            fn try_from(value: #repr) -> ::std::result::Result<Self, Self::Error> {
+                #(
+                    if value == Self::#idents as #repr {
+                        return ::std::result::Result::Ok(Self::#idents);
+                    }
+                )*
+                ::std::result::Result::Err(#error(value))
+            }
+        }
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -1,153 +1,395 @@
-use macro_functions::{get_segment_from_type, type_from_args};
+use macro_functions::{uses_type_params, FieldOptions};
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
 use syn::{
-    parse, parse2, parse_macro_input, parse_str, Field, Fields, FieldsNamed, GenericParam,
-    Generics, ItemEnum, ItemStruct, Meta, Path, PathSegment, TraitBound, TypeParam, TypeParamBound,
+    parse::ParseStream, parse_macro_input, parse_quote, punctuated::Punctuated, Attribute, Expr,
+    Field, Fields, Generics, Ident, ItemEnum, ItemStruct, LitStr, Member, Path, Token, Type,
+    WherePredicate,
 };
 
-pub fn expand(input: TokenStream) -> TokenStream {
-    let get_default_value = |field: &Field| {
-        let Field { ty, .. } = field;
-        field
-            .attrs
-            .iter()
-            .find_map(|attr| {
-                attr.path().is_ident("default").then(|| {
-                    let Meta::List(list) = &attr.meta else {
-                        unreachable!()
-                    };
-                    list.tokens.clone()
-                })
-            })
-            .unwrap_or_else(|| {
-                quote! { <#ty as std::default::Default>::default() }
-            })
-    };
+/// Значение поля по умолчанию, заданное атрибутом `#[default(...)]`.
+pub enum DefaultValue {
+    /// `#[default(expr)]`
+    Expr(TokenStream2),
+    /// `#[default(with = path::to_fn)]`
+    With(Path),
+    /// `#[default(env = "VAR", fallback = expr)]`
+    Env { var: LitStr, fallback: Option<Expr> },
+    /// `#[default(parse = "literal")]`
+    Parse(LitStr),
+}
 
-    match parse_macro_input!(input as syn::Item) {
-        syn::Item::Struct(ItemStruct {
-            ident,
-            fields,
-            generics,
-            ..
-        }) => {
-            let body = match fields {
-                Fields::Named(_) => {
-                    let field_defaults = fields.iter().map(|field| {
-                        let Field { ident, .. } = field;
-                        let default_value = get_default_value(field);
-                        quote! {
-                            #ident: #default_value
-                        }
-                    });
-                    quote! {
-                        {
-                            #(#field_defaults),*
+/// Реакция на ошибку получения значения по умолчанию.
+#[derive(Clone, Copy)]
+pub enum OnError {
+    /// Паника с сообщением об ошибке.
+    Panic,
+    /// Возврат `Err` из функции, возвращающей `Result<_, Box<dyn Error + Send + Sync>>`.
+    Return,
+}
+
+impl OnError {
+    fn handle(self, message: TokenStream2) -> TokenStream2 {
+        match self {
+            Self::Panic => quote!(::std::panic!("{}", #message)),
+            Self::Return => quote! {
+                return ::std::result::Result::Err(::std::convert::From::from(#message))
+            },
+        }
+    }
+}
+
+impl DefaultValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        if let Ok(ident) = fork.parse::<Ident>() {
+            if fork.peek(Token![=]) && !fork.peek(Token![==]) {
+                let parse_key = |input: ParseStream| {
+                    input.parse::<Ident>()?;
+                    input.parse::<Token![=]>()
+                };
+                match ident.to_string().as_str() {
+                    "with" => {
+                        parse_key(input)?;
+                        return input.parse().map(Self::With);
+                    }
+                    "parse" => {
+                        parse_key(input)?;
+                        return input.parse().map(Self::Parse);
+                    }
+                    "env" => {
+                        parse_key(input)?;
+                        let var = input.parse()?;
+                        let mut fallback = None;
+                        if input.parse::<Option<Token![,]>>()?.is_some() {
+                            let key: Ident = input.parse()?;
+                            if key != "fallback" {
+                                return Err(syn::Error::new_spanned(key, "expected `fallback`"));
+                            }
+                            input.parse::<Token![=]>()?;
+                            fallback = Some(input.parse()?);
                         }
+                        return Ok(Self::Env { var, fallback });
                     }
+                    _ => {}
                 }
-                Fields::Unnamed(_) => {
-                    let field_defaults = fields.iter().map(|field| get_default_value(field));
-                    quote! {
-                        (#(#field_defaults),*)
+            }
+        }
+        input.parse().map(Self::Expr)
+    }
+
+    /// Значение из атрибутов поля, `None` если атрибут не указан.
+    ///
+    /// Вместо `#[default(expr)]` можно указать `#[std_reset(default = expr)]`.
+    pub fn from_field(field: &Field) -> syn::Result<Option<Self>> {
+        let value = field
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("default"))
+            .map(|attr| attr.parse_args_with(Self::parse))
+            .transpose()?;
+        match (value, FieldOptions::parse(&field.attrs)?.default) {
+            (Some(_), Some(expr)) => Err(syn::Error::new_spanned(
+                expr,
+                "default value is already specified with `#[default(...)]`",
+            )),
+            (value, None) => Ok(value),
+            (None, Some(expr)) => Ok(Some(Self::Expr(quote!(#expr)))),
+        }
+    }
+
+    /// Может ли получение значения завершиться ошибкой.
+    pub fn is_fallible(&self) -> bool {
+        matches!(self, Self::Env { .. } | Self::Parse(_))
+    }
+
+    /// Выражение значения поля типа `ty`.
+    ///
+    /// `name` - имя поля в сообщениях об ошибках.
+    pub fn value(&self, ty: &Type, name: &str, on_error: OnError) -> TokenStream2 {
+        let from_str = |value: TokenStream2, source: String| {
+            let message = format!("failed to parse `{name}` from {source}: {{}}");
+            let error = on_error.handle(quote!(::std::format!(#message, err)));
+            quote! {
+                match <#ty as ::std::str::FromStr>::from_str(#value) {
+                    ::std::result::Result::Ok(value) => value,
+                    ::std::result::Result::Err(err) => #error,
+                }
+            }
+        };
+        match self {
+            Self::Expr(expr) => expr.clone(),
+            Self::With(path) => quote!(#path()),
+            Self::Parse(lit) => from_str(
+                quote!(#lit),
+                format!("{:?}", lit.value())
+                    .replace('{', "{{")
+                    .replace('}', "}}"),
+            ),
+            Self::Env { var, fallback } => {
+                let source = format!("environment variable `{}`", var.value())
+                    .replace('{', "{{")
+                    .replace('}', "}}");
+                let parsed = from_str(quote!(&value), source.clone());
+                let missing = match fallback {
+                    Some(fallback) => quote!(#fallback),
+                    None => {
+                        let message = format!("{source} for `{name}` is not set");
+                        on_error.handle(quote!(::std::string::String::from(#message)))
+                    }
+                };
+                let message = format!("failed to read {source} for `{name}`: {{}}");
+                let error = on_error.handle(quote!(::std::format!(#message, err)));
+                quote! {
+                    match ::std::env::var(#var) {
+                        ::std::result::Result::Ok(value) => #parsed,
+                        ::std::result::Result::Err(::std::env::VarError::NotPresent) => #missing,
+                        ::std::result::Result::Err(err) => #error,
                     }
                 }
-                Fields::Unit => proc_macro2::TokenStream::default(),
-            };
+            }
+        }
+    }
 
-            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    /// Выражение значения поля по умолчанию.
+    ///
+    /// Поле без атрибута получает значение [`Default::default`],
+    /// что недоступно в константном контексте.
+    pub fn of_field(
+        field: &Field,
+        name: &str,
+        is_const: bool,
+        on_error: OnError,
+    ) -> syn::Result<TokenStream2> {
+        let Field { ty, .. } = field;
+        match Self::from_field(field)? {
+            Some(value) if is_const && value.is_fallible() => Err(syn::Error::new_spanned(
+                field,
+                "`env` and `parse` cannot be used in `#[default(const)]` type",
+            )),
+            Some(value) => Ok(value.value(ty, name, on_error)),
+            None if is_const => Err(syn::Error::new_spanned(
+                field,
+                "a field of `#[default(const)]` type requires an explicit default value",
+            )),
+            None => Ok(quote! { <#ty as std::default::Default>::default() }),
+        }
+    }
+}
 
-            let mut generics = parse2::<Generics>(impl_generics.to_token_stream()).unwrap();
+/// Настройки из атрибута `#[default(...)]` структуры или перечисления.
+#[derive(Default)]
+pub struct DefaultConfig {
+    /// `#[default(const)]`
+    pub is_const: bool,
+    /// `#[default(bound = "...")]`
+    bound: Option<Punctuated<WherePredicate, Token![,]>>,
+}
 
-            generics.params.iter_mut().for_each(|type_| {
-                if let GenericParam::Type(type_param) = type_ {
-                    let TypeParam { bounds, .. } = type_param;
+impl DefaultConfig {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut config = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("default")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("const") {
+                    config.is_const = true;
+                    Ok(())
+                } else if meta.path.is_ident("bound") {
+                    let bound: LitStr = meta.value()?.parse()?;
+                    config.bound = Some(bound.parse_with(Punctuated::parse_terminated)?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `const` or `bound`"))
+                }
+            })?;
+        }
+        Ok(config)
+    }
 
-                    if !bounds.iter().any(|trait_| {
-                        if let TypeParamBound::Trait(TraitBound { path, .. }) = trait_ {
-                            if path.get_ident().unwrap() == "Default" {
-                                return true;
-                            }
+    /// Обобщения реализации с ограничениями на типы полей, которые зависят от параметров типа:
+    /// `Default` для полей без атрибута и `FromStr` для `env` и `parse`.
+    ///
+    /// Ограничения из `#[default(bound = "...")]` заменяют вычисленные.
+    pub fn bounds(&self, generics: &Generics, fields: &Fields) -> syn::Result<Generics> {
+        let mut generics = generics.clone();
+        let predicates = match &self.bound {
+            Some(bound) => bound.iter().cloned().collect(),
+            None => {
+                let mut predicates: Vec<WherePredicate> = vec![];
+                for field in fields {
+                    let Field { ty, .. } = field;
+                    if !uses_type_params(ty, &generics) {
+                        continue;
+                    }
+                    let field_predicates: Vec<WherePredicate> = match DefaultValue::from_field(
+                        field,
+                    )? {
+                        None => vec![parse_quote!(#ty: std::default::Default)],
+                        Some(value) if value.is_fallible() => vec![
+                            parse_quote!(#ty: ::std::str::FromStr),
+                            parse_quote!(<#ty as ::std::str::FromStr>::Err: ::std::fmt::Display),
+                        ],
+                        Some(_) => vec![],
+                    };
+                    for predicate in field_predicates {
+                        if !predicates.contains(&predicate) {
+                            predicates.push(predicate);
                         }
-                        false
-                    }) {
-                        bounds.push(parse_str::<TypeParamBound>("Default").unwrap())
                     }
                 }
-            });
-            
+                predicates
+            }
+        };
+        generics.make_where_clause().predicates.extend(predicates);
+        Ok(generics)
+    }
+}
+
+/// Значения по умолчанию для всех полей структуры или варианта.
+///
+/// `owner` - имя структуры или варианта в сообщениях об ошибках.
+pub fn fields_body(
+    owner: &str,
+    fields: &Fields,
+    is_const: bool,
+    on_error: OnError,
+) -> syn::Result<TokenStream2> {
+    let field_defaults = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::from(i),
+            };
+            let name = format!("{owner}.{}", quote!(#member));
+            DefaultValue::of_field(field, &name, is_const, on_error)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(match fields {
+        Fields::Named(_) => {
+            let idents = fields.iter().map(|field| &field.ident);
             quote! {
-                impl #generics std::default::Default for #ident #ty_generics #where_clause {
-                    fn default() -> Self {
-                        Self #body
-                    }
+                { #(#idents: #field_defaults),* }
+            }
+        }
+        Fields::Unnamed(_) => quote! {
+            ( #(#field_defaults),* )
+        },
+        Fields::Unit => TokenStream2::default(),
+    })
+}
+
+/// Реализация `Default`, а также `DEFAULT`, `try_default` и `default_with`.
+///
+/// `path` - путь к структуре или варианту со значениями по умолчанию.
+fn impls(
+    ident: &Ident,
+    generics: &Generics,
+    path: TokenStream2,
+    owner: &str,
+    fields: &Fields,
+    config: &DefaultConfig,
+) -> syn::Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = fields_body(owner, fields, config.is_const, OnError::Panic)?;
+    let (const_, value) = match config.is_const {
+        true => (
+            quote! {
+                /// Значение по умолчанию, доступное в константном контексте.
+                pub const DEFAULT: Self = #path #body;
+            },
+            quote!(Self::DEFAULT),
+        ),
+        false => (TokenStream2::default(), quote!(#path #body)),
+    };
+    let mut is_fallible = false;
+    for field in fields {
+        is_fallible |= DefaultValue::from_field(field)?.is_some_and(|value| value.is_fallible());
+    }
+    let try_default = match is_fallible {
+        true => {
+            let body = fields_body(owner, fields, false, OnError::Return)?;
+            quote! {
+                /// Значение по умолчанию или ошибка чтения переменной окружения
+                /// либо разбора значения поля.
+                pub fn try_default() -> ::std::result::Result<
+                    Self,
+                    ::std::boxed::Box<dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync>,
+                > {
+                    ::std::result::Result::Ok(#path #body)
                 }
             }
-            .into()
+        }
+        false => TokenStream2::default(),
+    };
+    Ok(quote! {
+        impl #impl_generics std::default::Default for #ident #ty_generics #where_clause {
+            fn default() -> Self {
+                #value
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #const_
+
+            #try_default
+
+            /// Значение по умолчанию с изменениями, внесёнными `f`.
+            pub fn default_with(f: impl FnOnce(&mut Self)) -> Self {
+                let mut value = <Self as std::default::Default>::default();
+                f(&mut value);
+                value
+            }
+        }
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let (attrs, ident, generics, path, owner, fields) = match parse_macro_input!(input as syn::Item)
+    {
+        syn::Item::Struct(ItemStruct {
+            attrs,
+            ident,
+            fields,
+            generics,
+            ..
+        }) => {
+            let owner = ident.to_string();
+            (attrs, ident, generics, quote!(Self), owner, fields)
         }
         syn::Item::Enum(ItemEnum {
+            attrs,
             variants,
             generics,
             ident,
             ..
         }) => {
             let default_variant = variants
-                .iter()
+                .into_iter()
                 .find(|v| v.attrs.iter().any(|attr| attr.path().is_ident("default")))
                 .expect("enumeration must have the attribute #[default]");
             let variant_ident = &default_variant.ident;
-            let body = match &default_variant.fields {
-                Fields::Named(fields_named) => {
-                    let field_defaults = fields_named.named.iter().map(|field| {
-                        let ident = &field.ident;
-                        let default_value = get_default_value(field);
-                        quote! {
-                            #ident: #default_value
-                        }
-                    });
-                    quote! {
-                        { #(#field_defaults),* }
-                    }
-                }
-                Fields::Unnamed(fields_unnamed) => {
-                    let field_defaults = fields_unnamed
-                        .unnamed
-                        .iter()
-                        .map(|field| get_default_value(field));
-                    quote! {
-                        ( #(#field_defaults),* )
-                    }
-                }
-                Fields::Unit => proc_macro2::TokenStream::default(),
-            };
-            let (_, ty_generics, where_clause) = generics.split_for_impl();
-            let mut generics = generics.clone();
-
-            generics.params.iter_mut().for_each(|type_| {
-                if let GenericParam::Type(type_param) = type_ {
-                    let TypeParam { bounds, .. } = type_param;
-                    if !bounds.iter().any(|trait_| {
-                        if let TypeParamBound::Trait(TraitBound { path, .. }) = trait_ {
-                            path.is_ident("Default")
-                        } else {
-                            false
-                        }
-                    }) {
-                        bounds.push(parse_str::<TypeParamBound>("Default").unwrap())
-                    }
-                }
-            });
-
-            quote! {
-                impl #generics std::default::Default for #ident #ty_generics #where_clause {
-                    fn default() -> Self {
-                        Self::#variant_ident #body
-                    }
-                }
-            }
-            .into()
+            let owner = format!("{ident}::{variant_ident}");
+            (
+                attrs,
+                ident,
+                generics,
+                quote!(Self::#variant_ident),
+                owner,
+                default_variant.fields,
+            )
         }
         _ => panic!("Этот макрос поддерживает только структуры и перечисления"),
+    };
+
+    let expanded = DefaultConfig::parse(&attrs).and_then(|config| {
+        let generics = config.bounds(&generics, &fields)?;
+        impls(&ident, &generics, path, &owner, &fields, &config)
+    });
+    match expanded {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
     }
 }
@@ -1,73 +1,265 @@
-use macro_functions::{get_segment_from_type, type_from_args};
+use macro_functions::{deref_target, FieldOptions};
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::quote;
-use syn::{parse_macro_input, Field, Fields, FieldsNamed, FieldsUnnamed, ItemStruct, PathSegment};
+use syn::{
+    parse_macro_input, Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Field,
+    Fields, FieldsNamed, FieldsUnnamed, Meta, Token, Type,
+};
 
-pub fn expand(input: TokenStream) -> TokenStream {
-    let ItemStruct {
-        fields,
-        ident,
-        generics,
-        ..
-    } = parse_macro_input!(input);
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+/// Настройки `#[deref(...)]` структуры или поля.
+#[derive(Default)]
+struct DerefConfig {
+    /// `target = Type` - тип, к которому разыменовывается поле,
+    /// `target` без значения - `str` для `String`, `[T]` для `Vec<T>` и т.д.
+    target: Option<Option<Type>>,
+    /// `readonly` - без реализации `DerefMut`.
+    is_readonly: bool,
+    /// `forward` - разыменование через `Deref` самого поля.
+    is_forward: bool,
+}
 
-    let (field, pos) = match fields {
-        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-            let pos = if unnamed.len() > 1 {
-                let deref_fields = unnamed
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, field)| field.attrs.iter().any(|attr| attr.path().is_ident("deref")))
-                    .collect::<Vec<_>>();
-
-                if deref_fields.len() > 1 {
-                    panic!("only one field can be marked with the attribute #[deref]");
-                } else if deref_fields.len() == 0 {
-                    panic!("unnamed fields must be 1, or specify the main field using the attribute #[deref]");
+impl DerefConfig {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut config = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("deref")) {
+            if !matches!(attr.meta, Meta::List(_)) {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("target") {
+                    config.target = Some(if meta.input.peek(Token![=]) {
+                        Some(meta.value()?.parse()?)
+                    } else {
+                        None
+                    });
+                } else if meta.path.is_ident("readonly") {
+                    config.is_readonly = true;
+                } else if meta.path.is_ident("forward") {
+                    config.is_forward = true;
                 } else {
-                    deref_fields[0].0
+                    return Err(meta.error("unsupported deref attribute"));
                 }
-            } else {
-                0
-            };
-            (unnamed[pos].clone(), {
-                let pos = syn::Index::from(pos);
-                quote! {#pos}
-            })
+                Ok(())
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// Настройки поля дополняются настройками структуры.
+    fn or(self, outer: Self) -> Self {
+        Self {
+            target: self.target.or(outer.target),
+            is_readonly: self.is_readonly || outer.is_readonly,
+            is_forward: self.is_forward || outer.is_forward,
         }
-        Fields::Named(FieldsNamed { named, .. }) => {
-            let deref_fields = named
-                .iter()
-                .filter(|field| field.attrs.iter().any(|attr| attr.path().is_ident("deref")))
-                .collect::<Vec<_>>();
-            if deref_fields.len() > 1 {
-                panic!("only one field can be marked with the attribute #[deref]");
-            } else if deref_fields.len() == 0 {
-                panic!("specify the main field using the attribute #[deref]");
-            } else {
-                let y = deref_fields[0].ident.clone();
-                (deref_fields[0].clone(), quote! {#y})
+    }
+}
+
+/// Поле, к которому разыменовывается структура или вариант: единственное поле
+/// или поле, отмеченное атрибутом `#[deref]`.
+pub fn deref_field(fields: &Fields) -> syn::Result<(Field, proc_macro2::TokenStream)> {
+    let marked: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.attrs.iter().any(|attr| attr.path().is_ident("deref")))
+        .collect();
+    let (pos, field) =
+        match (&marked[..], fields) {
+            ([_, second, ..], _) => {
+                return Err(syn::Error::new_spanned(
+                    second.1,
+                    "only one field can be marked with the attribute #[deref]",
+                ))
+            }
+            ([marked], _) => *marked,
+            ([], Fields::Unnamed(FieldsUnnamed { unnamed, .. })) if unnamed.len() == 1 => {
+                (0, &unnamed[0])
+            }
+            ([], Fields::Unnamed(_)) => return Err(syn::Error::new_spanned(
+                fields,
+                "unnamed fields must be 1, or specify the main field using the attribute #[deref]",
+            )),
+            ([], Fields::Named(FieldsNamed { named, .. })) if named.len() == 1 => (0, &named[0]),
+            ([], Fields::Named(_)) => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "specify the main field using the attribute #[deref]",
+                ))
+            }
+            ([], Fields::Unit) => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "a field to dereference to is required",
+                ))
             }
+        };
+    let pos = match &field.ident {
+        Some(ident) => quote!(#ident),
+        None => {
+            let pos = syn::Index::from(pos);
+            quote!(#pos)
         }
-        _ => panic!(),
     };
-    let Field { ty, .. } = field.clone();
+    Ok((field.clone(), pos))
+}
 
-    quote! {
-        impl #impl_generics std::ops::Deref for #ident #ty_generics #where_clause {
-            type Target = #ty;
+/// Поле разыменования структуры или каждого варианта перечисления.
+struct Target {
+    ty: Type,
+    /// Настройки `#[deref(...)]` поля, для перечисления - объединённые по всем вариантам.
+    config: DerefConfig,
+    /// Образцы `Self { поле: value, .. }`, которые связывают поле с `value`.
+    patterns: Vec<proc_macro2::TokenStream>,
+}
 
-            fn deref(&self) -> &Self::Target {
-                &self. #pos
+impl Target {
+    fn collect(input: &DeriveInput) -> syn::Result<Self> {
+        let mut fields = vec![];
+        match &input.data {
+            Data::Struct(DataStruct {
+                fields: struct_fields,
+                ..
+            }) => {
+                if let Fields::Unit = struct_fields {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident,
+                        "unit structs are not supported",
+                    ));
+                }
+                fields.push((quote!(Self), struct_fields));
+            }
+            Data::Enum(DataEnum { variants, .. }) => {
+                if variants.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident,
+                        "enums without variants are not supported",
+                    ));
+                }
+                for variant in variants {
+                    if let Fields::Unit = variant.fields {
+                        return Err(syn::Error::new_spanned(
+                            variant,
+                            "every variant must have a field to dereference to",
+                        ));
+                    }
+                    let variant_ident = &variant.ident;
+                    fields.push((quote!(Self::#variant_ident), &variant.fields));
+                }
+            }
+            Data::Union(DataUnion { union_token, .. }) => {
+                return Err(syn::Error::new_spanned(
+                    union_token,
+                    "unions are not supported",
+                ))
+            }
+        }
+
+        let mut target: Option<Self> = None;
+        for (path, fields) in fields {
+            let (field, pos) = deref_field(fields)?;
+            FieldOptions::parse_checked(&field.attrs, "Deref", &["skip"])?;
+            let pattern = quote!(#path { #pos: value, .. });
+            let config = DerefConfig::parse(&field.attrs)?;
+            match &mut target {
+                None => {
+                    target = Some(Self {
+                        ty: field.ty,
+                        config,
+                        patterns: vec![pattern],
+                    })
+                }
+                Some(target) if target.ty != field.ty => {
+                    return Err(syn::Error::new_spanned(
+                        &field.ty,
+                        "every variant must dereference to a field of the same type",
+                    ))
+                }
+                Some(target) => {
+                    target.config = config.or(std::mem::take(&mut target.config));
+                    target.patterns.push(pattern);
+                }
             }
         }
+        let mut target = target.unwrap();
+        target.config = target.config.or(DerefConfig::parse(&input.attrs)?);
+        Ok(target)
+    }
 
-        impl #impl_generics std::ops::DerefMut for #ident #ty_generics #where_clause {
-            fn deref_mut(&mut self) -> &mut Self::Target {
-                &mut self. #pos
+    /// Тело метода, который получает ссылку `value` на поле и возвращает `conv`.
+    fn access(&self, conv: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let patterns = &self.patterns;
+        quote! {
+            match self {
+                #(#patterns => #conv,)*
             }
         }
     }
-    .into()
+}
+
+fn try_expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    FieldOptions::validate(&input.data, "Deref", &[])?;
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let target = Target::collect(input)?;
+    let Target { ty, config, .. } = &target;
+
+    let (target_ty, deref, deref_mut) = match (&config.target, config.is_forward) {
+        (Some(_), true) => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`target` and `forward` cannot be used together",
+            ))
+        }
+        (Some(target_ty), false) => {
+            let Some(target_ty) = target_ty.clone().or_else(|| deref_target(ty)) else {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "specify the target type with `target = Type`",
+                ));
+            };
+            (quote!(#target_ty), quote!(value), quote!(value))
+        }
+        (None, true) => (
+            quote!(<#ty as std::ops::Deref>::Target),
+            quote!(std::ops::Deref::deref(value)),
+            quote!(std::ops::DerefMut::deref_mut(value)),
+        ),
+        (None, false) => (quote!(#ty), quote!(value), quote!(value)),
+    };
+    let deref = target.access(deref);
+    let deref_mut = target.access(deref_mut);
+
+    let deref_mut = (!config.is_readonly).then(|| {
+        quote! {
+            impl #impl_generics std::ops::DerefMut for #ident #ty_generics #where_clause {
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    #deref_mut
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics std::ops::Deref for #ident #ty_generics #where_clause {
+            type Target = #target_ty;
+
+            fn deref(&self) -> &Self::Target {
+                #deref
+            }
+        }
+
+        #deref_mut
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
 }
@@ -1,21 +1,371 @@
+use macro_functions::{uses_type_params, FieldOptions};
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, ItemStruct};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    parse_quote, Attribute, Data, DataUnion, DeriveInput, Expr, Fields, Generics, Ident, LitStr,
+    Member, Token, Type, WherePredicate,
+};
 
-use macro_functions::fast_impl;
+/// Содержимое атрибута `#[display(...)]`.
+enum Format {
+    /// `#[display("...", args...)]`
+    Lit(LitStr, Punctuated<Expr, Token![,]>),
+    /// `#[display(skip)]`
+    Skip,
+}
+
+impl Parse for Format {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            let lit = input.parse()?;
+            let args = match input.parse::<Option<Token![,]>>()? {
+                Some(_) => Punctuated::parse_terminated(input)?,
+                None => Punctuated::new(),
+            };
+            return Ok(Self::Lit(lit, args));
+        }
+        let ident: Ident = input.parse()?;
+        if ident != "skip" {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "expected a format string or `skip`",
+            ));
+        }
+        Ok(Self::Skip)
+    }
+}
+
+impl Format {
+    fn parse_attrs(attrs: &[Attribute], attr_name: &str) -> syn::Result<Option<Self>> {
+        let mut attrs = attrs.iter().filter(|attr| attr.path().is_ident(attr_name));
+        let Some(attr) = attrs.next() else {
+            return Ok(None);
+        };
+        if let Some(attr) = attrs.next() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("duplicate `{attr_name}` attribute"),
+            ));
+        }
+        attr.parse_args().map(Some)
+    }
+}
+
+/// Имя, под которым поле доступно в строке формата.
+fn binding(member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => ident.clone(),
+        Member::Unnamed(index) => format_ident!("_{}", index.index),
+    }
+}
+
+/// Заменяет в строке формата ссылки `{0}` и `{}` на поля кортежа `_0`, `_1`, ...
+fn rewrite_positional(lit: &LitStr) -> LitStr {
+    let value = lit.value();
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    let mut next = 0;
+    while let Some(ch) = chars.next() {
+        result.push(ch);
+        if ch != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            result.push(chars.next().unwrap());
+            continue;
+        }
+        let mut arg = String::new();
+        while let Some(&ch) = chars.peek() {
+            if ch == ':' || ch == '}' {
+                break;
+            }
+            arg.push(ch);
+            chars.next();
+        }
+        let arg = arg.trim();
+        if arg.is_empty() {
+            result.push_str(&format!("_{next}"));
+            next += 1;
+        } else if arg.chars().all(|ch| ch.is_ascii_digit()) {
+            result.push_str(&format!("_{arg}"));
+        } else {
+            result.push_str(arg);
+        }
+    }
+    LitStr::new(&result, lit.span())
+}
+
+/// Именованные аргументы строки формата и трейты, через которые они выводятся.
+fn format_traits(lit: &LitStr) -> Vec<(String, TokenStream2)> {
+    let value = lit.value();
+    let mut traits = vec![];
+    let mut chars = value.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+        let mut arg = String::new();
+        while let Some(ch) = chars.next_if(|&ch| ch != '}') {
+            arg.push(ch);
+        }
+        let (arg, spec) = arg.split_once(':').unwrap_or((&arg, ""));
+        let format_trait = match spec.chars().last() {
+            Some('?') => quote!(Debug),
+            Some('x') => quote!(LowerHex),
+            Some('X') => quote!(UpperHex),
+            Some('o') => quote!(Octal),
+            Some('b') => quote!(Binary),
+            Some('e') => quote!(LowerExp),
+            Some('E') => quote!(UpperExp),
+            _ => quote!(Display),
+        };
+        traits.push((arg.trim().to_string(), format_trait));
+    }
+    traits
+}
+
+/// Вывод структуры или варианта перечисления.
+///
+/// `variant` - вариант перечисления, `None` для структуры.
+/// `is_transparent` - вывод единственного поля без изменений.
+/// В `predicates` добавляются ограничения для полей, тип которых зависит от параметров `input`.
+fn write_fields(
+    input: &DeriveInput,
+    fields: &Fields,
+    variant: Option<&Ident>,
+    format: Option<Format>,
+    is_transparent: bool,
+    attr_name: &str,
+    predicates: &mut Vec<WherePredicate>,
+) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let mut bound = |ty: &Type, format_trait: TokenStream2| {
+        if uses_type_params(ty, generics) {
+            predicates.push(parse_quote!(#ty: ::std::fmt::#format_trait));
+        }
+    };
+    let path = match variant {
+        Some(variant) => quote!(Self::#variant),
+        None => quote!(Self),
+    };
+    if is_transparent {
+        if let Some(Format::Lit(lit, _)) = format {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "format string cannot be used with `transparent`",
+            ));
+        }
+        if fields.len() != 1 {
+            return Err(syn::Error::new_spanned(
+                variant.unwrap_or(ident),
+                "`transparent` requires exactly one field",
+            ));
+        }
+        let field = fields.iter().next().unwrap();
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::from(0),
+        };
+        bound(&field.ty, quote!(Display));
+        return Ok(quote! {
+            #path { #member: __inner } => ::std::fmt::Display::fmt(__inner, __formatter),
+        });
+    }
+
+    let mut members = vec![];
+    let mut tys = vec![];
+    let mut names = vec![];
+    let mut skipped = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::from(i),
+        };
+        let options = FieldOptions::parse(&field.attrs)?;
+        match Format::parse_attrs(&field.attrs, attr_name)? {
+            _ if options.skip => skipped.push(member),
+            None => {
+                names.push(options.name(quote!(#member).to_string()));
+                members.push(member);
+                tys.push(&field.ty);
+            }
+            Some(Format::Skip) => skipped.push(member),
+            Some(Format::Lit(lit, _)) => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "format string is not allowed on fields",
+                ))
+            }
+        }
+    }
+    let bindings: Vec<_> = members.iter().map(binding).collect();
+    let patterns = members
+        .iter()
+        .zip(&bindings)
+        .map(|(member, binding)| match member {
+            Member::Named(_) => quote!(#binding),
+            Member::Unnamed(_) => quote!(#member: #binding),
+        });
+    let pattern = quote!(#path { #(#patterns,)* .. });
+
+    let body = match format {
+        Some(Format::Skip) => quote!(::std::fmt::Result::Ok(())),
+        Some(Format::Lit(lit, args)) => {
+            let lit = match (fields, args.is_empty()) {
+                (Fields::Unnamed(_), true) => rewrite_positional(&lit),
+                _ => lit,
+            };
+            // ограничения выводятся только для полей, которые строка формата упоминает по имени
+            let named_args: Vec<_> = args
+                .iter()
+                .filter_map(|arg| match arg {
+                    Expr::Assign(assign) => Some(&assign.left),
+                    _ => None,
+                })
+                .map(|left| quote!(#left).to_string())
+                .collect();
+            for (arg, format_trait) in format_traits(&lit) {
+                if named_args.contains(&arg) {
+                    continue;
+                }
+                if let Some(i) = bindings.iter().position(|binding| *binding == arg) {
+                    bound(tys[i], format_trait);
+                }
+            }
+            quote!(::std::write!(__formatter, #lit, #args))
+        }
+        None if skipped.is_empty() => {
+            if generics.type_params().next().is_some() {
+                let (_, ty_generics, _) = generics.split_for_impl();
+                predicates.push(parse_quote!(#ident #ty_generics: ::std::fmt::Debug));
+            }
+            quote!(::std::fmt::Debug::fmt(self, __formatter))
+        }
+        None => {
+            tys.iter().for_each(|ty| bound(ty, quote!(Debug)));
+            let name = variant.unwrap_or(ident).to_string();
+            match fields {
+                Fields::Named(_) => {
+                    let names = names
+                        .iter()
+                        .map(|name| LitStr::new(name, Span::call_site()));
+                    quote! {
+                        __formatter.debug_struct(#name)
+                            #(.field(#names, #bindings))*
+                            .finish_non_exhaustive()
+                    }
+                }
+                _ => quote! {
+                    __formatter.debug_tuple(#name)
+                        #(.field(#bindings))*
+                        .finish()
+                },
+            }
+        }
+    };
+    Ok(quote!(#pattern => #body,))
+}
+
+/// Тело метода `fmt`, записывающего в `__formatter` согласно атрибутам `attr_name`.
+///
+/// `is_transparent` определяет по атрибутам структуры или варианта,
+/// нужно ли выводить их единственное поле без изменений.
+/// Вместе с телом возвращаются параметры `input` с ограничениями для выводимых полей.
+pub fn fmt_body(
+    input: &DeriveInput,
+    attr_name: &str,
+    is_transparent: impl Fn(&[Attribute]) -> syn::Result<bool>,
+) -> syn::Result<(TokenStream2, Generics)> {
+    let format = Format::parse_attrs(&input.attrs, attr_name)?;
+    if let Some(Format::Skip) = format {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("`{attr_name}(skip)` is only allowed on variants and fields"),
+        ));
+    }
+    let mut predicates = vec![];
+    let arms = match &input.data {
+        Data::Struct(data) => {
+            vec![write_fields(
+                input,
+                &data.fields,
+                None,
+                format,
+                is_transparent(&input.attrs)?,
+                attr_name,
+                &mut predicates,
+            )?]
+        }
+        Data::Enum(data) => {
+            if let Some(Format::Lit(lit, _)) = format {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "format string must be specified on each variant",
+                ));
+            }
+            data.variants
+                .iter()
+                .map(|variant| {
+                    let format = Format::parse_attrs(&variant.attrs, attr_name)?;
+                    write_fields(
+                        input,
+                        &variant.fields,
+                        Some(&variant.ident),
+                        format,
+                        is_transparent(&variant.attrs)?,
+                        attr_name,
+                        &mut predicates,
+                    )
+                })
+                .collect::<syn::Result<_>>()?
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    if arms.is_empty() {
+        return Ok((quote!(match *self {}), generics));
+    }
+    let body = quote! {
+        #[allow(unused_variables)]
+        match self {
+            #(#arms)*
+        }
+    };
+    Ok((body, generics))
+}
 
 pub fn expand(item: TokenStream) -> TokenStream {
-    let struct_: ItemStruct = parse_macro_input!(item);
+    let input: DeriveInput = parse_macro_input!(item);
+    let (body, generics) = match FieldOptions::validate(&input.data, "Display", &[])
+        .and_then(|()| fmt_body(&input, "display", |_| Ok(false)))
+    {
+        Ok(output) => output,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
-    let ItemStruct {
-        generics, ident, ..
-    } = struct_;
+    let ident = &input.ident;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     quote! {
         impl #impl_generics std::fmt::Display for #ident #ty_generics #where_clause {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                std::fmt::Debug::fmt(self, f)
+            fn fmt(&self, __formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #body
             }
         }
     }
@@ -0,0 +1,247 @@
+use macro_functions::{Case, FieldOptions};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_macro_input, Attribute, Data, DataEnum, DataStruct, DataUnion,
+    DeriveInput, Fields, LitStr, Member, Variant,
+};
+
+/// Настройки из атрибута `#[enum_utils(...)]`.
+#[derive(Default)]
+struct EnumConfig {
+    /// `case = "..."`, только для перечисления.
+    case: Option<Case>,
+    /// `rename = "..."`, только для варианта.
+    rename: Option<LitStr>,
+}
+
+impl EnumConfig {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut config = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("enum_utils"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("case") {
+                    let case: LitStr = meta.value()?.parse()?;
+                    config.case = Some(Case::from_name(&case.value()).ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            &case,
+                            "expected one of: PascalCase, camelCase, snake_case, \
+                             SCREAMING_SNAKE_CASE, kebab-case, lowercase, UPPERCASE",
+                        )
+                    })?);
+                } else if meta.path.is_ident("rename") {
+                    config.rename = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `case` or `rename`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(config)
+    }
+}
+
+/// Методы `as_<вариант>` и `into_<вариант>` для варианта с данными.
+fn accessors(variant: &Variant, snake: &str) -> Option<TokenStream2> {
+    let Variant { ident, fields, .. } = variant;
+    if let Fields::Unit = fields {
+        return None;
+    }
+    let members = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::from(i),
+        });
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__{}", i))
+        .collect();
+    let tys: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let pattern = quote!(Self::#ident { #(#members: #bindings),* });
+    let (ref_ty, ref_value, ty, value) = match fields.len() {
+        1 => (
+            quote!(&#(#tys)*),
+            quote!(#(#bindings)*),
+            quote!(#(#tys)*),
+            quote!(#(#bindings)*),
+        ),
+        _ => (
+            quote!((#(&#tys),*)),
+            quote!((#(#bindings),*)),
+            quote!((#(#tys),*)),
+            quote!((#(#bindings),*)),
+        ),
+    };
+    let as_ = format_ident!("as_{}", snake);
+    let into = format_ident!("into_{}", snake);
+    let as_doc = format!(" Ссылки на данные варианта `{ident}`.");
+    let into_doc = format!(" Данные варианта `{ident}`.");
+    Some(quote! {
+        #[doc = #as_doc]
+        pub fn #as_(&self) -> ::std::option::Option<#ref_ty> {
+            match self {
+                #pattern => ::std::option::Option::Some(#ref_value),
+                #[allow(unreachable_patterns)]
+                _ => ::std::option::Option::None,
+            }
+        }
+
+        #[doc = #into_doc]
+        pub fn #into(self) -> ::std::option::Option<#ty> {
+            match self {
+                #pattern => ::std::option::Option::Some(#value),
+                #[allow(unreachable_patterns)]
+                _ => ::std::option::Option::None,
+            }
+        }
+    })
+}
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let variants = match data {
+        Data::Enum(DataEnum { variants, .. }) => variants,
+        Data::Struct(DataStruct { struct_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                struct_token,
+                "structs are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+    let case = EnumConfig::parse(attrs)?.case;
+
+    let mut idents = vec![];
+    let mut names = vec![];
+    let mut methods = vec![];
+    for variant in variants {
+        let config = EnumConfig::parse(&variant.attrs)?;
+        if config.case.is_some() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`case` can be specified only for the enum",
+            ));
+        }
+        let variant_ident = &variant.ident;
+        let declared = variant_ident.unraw().to_string();
+        let options = FieldOptions::parse_checked(&variant.attrs, "EnumUtils", &["skip"])?;
+        FieldOptions::validate_fields(&variant.fields, "EnumUtils", &[])?;
+        let rename = config.rename.or(options.rename);
+        let name = match (&rename, case) {
+            (Some(rename), _) => rename.value(),
+            (None, Some(case)) => case.convert(&declared),
+            (None, None) => declared.clone(),
+        };
+        if let Some(i) = names.iter().position(|other| *other == name) {
+            let message = format!(
+                "variants `{}` and `{variant_ident}` have the same name `{name}`",
+                idents[i]
+            );
+            return Err(match &rename {
+                Some(rename) => syn::Error::new_spanned(rename, message),
+                None => syn::Error::new_spanned(variant_ident, message),
+            });
+        }
+        let snake = Case::Snake.convert(&declared);
+        let is = format_ident!("is_{}", snake);
+        let is_doc = format!(" Является ли значение вариантом `{variant_ident}`.");
+        methods.push(quote! {
+            #[doc = #is_doc]
+            pub fn #is(&self) -> bool {
+                ::std::matches!(self, Self::#variant_ident { .. })
+            }
+        });
+        methods.extend(accessors(variant, &snake));
+        idents.push(variant_ident);
+        names.push(name);
+    }
+    let count = variants.len();
+    let is_unit = variants
+        .iter()
+        .all(|variant| matches!(variant.fields, Fields::Unit));
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let unit = is_unit.then(|| {
+        let error = format_ident!("Parse{ident}Error");
+        let error_doc = format!(" Ошибка разбора [`{ident}`] из строки.");
+        let message = format!("unknown variant `{{}}` of `{ident}`");
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Все варианты в порядке объявления.
+                pub const VARIANTS: &'static [Self] = &[#(Self::#idents),*];
+
+                /// Итератор по вариантам в порядке объявления.
+                pub fn iter() -> ::std::slice::Iter<'static, Self> {
+                    Self::VARIANTS.iter()
+                }
+            }
+
+            #[doc = #error_doc]
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #vis struct #error(pub ::std::string::String);
+
+            impl ::std::fmt::Display for #error {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::write!(f, #message, self.0)
+                }
+            }
+
+            impl ::std::error::Error for #error {}
+
+            impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+                type Err = #error;
+
+                fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                    match s {
+                        #(#names => ::std::result::Result::Ok(Self::#idents),)*
+                        _ => ::std::result::Result::Err(#error(::std::string::ToString::to_string(s))),
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Количество вариантов.
+            pub const COUNT: usize = #count;
+
+            /// Имя варианта.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    #(Self::#idents { .. } => #names,)*
+                }
+            }
+
+            #(#methods)*
+        }
+
+        #unit
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
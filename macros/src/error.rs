@@ -0,0 +1,267 @@
+use macro_functions::{uses_type_params, FieldOptions};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DataUnion, DeriveInput, Fields, Ident, Member,
+    Type,
+};
+
+use crate::display::fmt_body;
+
+/// Отмечена ли структура или вариант атрибутом `#[error(transparent)]`.
+fn is_transparent(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut is_transparent = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("error")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("transparent") {
+                is_transparent = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `transparent`"))
+            }
+        })?;
+    }
+    Ok(is_transparent)
+}
+
+/// Вспомогательный трейт, приводящий источник к `dyn Error`: и типы с [`Error`](std::error::Error),
+/// и сами `dyn Error` из `Box<dyn Error + Send + Sync>`, для которого `Error` не реализован.
+fn as_dyn_error() -> TokenStream2 {
+    let dyn_errors = [
+        quote!(dyn ::std::error::Error + 'static),
+        quote!(dyn ::std::error::Error + ::std::marker::Send + 'static),
+        quote!(dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static),
+    ];
+    quote! {
+        trait __AsDynError {
+            fn __as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static);
+        }
+
+        impl<T: ::std::error::Error + 'static> __AsDynError for T {
+            fn __as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static) {
+                self
+            }
+        }
+
+        #(
+            impl __AsDynError for #dyn_errors {
+                fn __as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static) {
+                    self
+                }
+            }
+        )*
+    }
+}
+
+/// Поле-источник ошибки структуры или варианта.
+struct Source<'a> {
+    member: Member,
+    ty: &'a Type,
+    is_from: bool,
+    is_transparent: bool,
+}
+
+impl<'a> Source<'a> {
+    fn find(fields: &'a Fields, attrs: &[Attribute], span: &Ident) -> syn::Result<Option<Self>> {
+        let is_transparent = is_transparent(attrs)?;
+        let mut source = None;
+        for (i, field) in fields.iter().enumerate() {
+            let is_from = field.attrs.iter().any(|attr| attr.path().is_ident("from"));
+            let is_source = field
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("source"));
+            if !is_from && !is_source && !is_transparent {
+                continue;
+            }
+            if source.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "only one field can be marked as `#[source]` or `#[from]`",
+                ));
+            }
+            source = Some(Self {
+                member: match &field.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::from(i),
+                },
+                ty: &field.ty,
+                is_from,
+                is_transparent,
+            });
+        }
+        if let Some(Self { is_from: true, .. }) = source {
+            if fields.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    span,
+                    "`#[from]` requires the field to be the only one",
+                ));
+            }
+        }
+        if is_transparent && source.is_none() {
+            return Err(syn::Error::new_spanned(
+                span,
+                "`transparent` requires exactly one field",
+            ));
+        }
+        Ok(source)
+    }
+
+    /// Ветка `match` метода `source`.
+    ///
+    /// Источник приводится к `dyn Error` через трейт из [`as_dyn_error`].
+    fn arm(&self, path: TokenStream2) -> TokenStream2 {
+        let Self { member, .. } = self;
+        // вызов через точку разыменовывает `Box<dyn Error>` до `dyn Error`
+        let source = match self.is_transparent {
+            true => quote!(::std::error::Error::source(__source.__as_dyn_error())),
+            false => quote!(::std::option::Option::Some(__source.__as_dyn_error())),
+        };
+        quote!(#path { #member: __source, .. } => #source,)
+    }
+
+    /// Реализация `From` для поля с `#[from]`.
+    fn from(&self, input: &DeriveInput, path: TokenStream2) -> Option<TokenStream2> {
+        if !self.is_from {
+            return None;
+        }
+        let Self { member, ty, .. } = self;
+        let DeriveInput {
+            ident, generics, ..
+        } = input;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        Some(quote! {
+            impl #impl_generics ::std::convert::From<#ty> for #ident #ty_generics #where_clause {
+                fn from(source: #ty) -> Self {
+                    #path { #member: source }
+                }
+            }
+        })
+    }
+}
+
+/// Задан ли вывод ошибки атрибутом `#[display(...)]` или `#[error(transparent)]`.
+fn has_display(input: &DeriveInput) -> bool {
+    let is_display = |attrs: &[Attribute]| {
+        attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("display") || attr.path().is_ident("error"))
+    };
+    match &input.data {
+        Data::Enum(data) => {
+            is_display(&input.attrs)
+                || data
+                    .variants
+                    .iter()
+                    .any(|variant| is_display(&variant.attrs))
+        }
+        _ => is_display(&input.attrs),
+    }
+}
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    FieldOptions::validate(&input.data, "Error", &[])?;
+
+    let sources: Vec<_> = match &input.data {
+        Data::Struct(data) => Source::find(&data.fields, &input.attrs, &input.ident)?
+            .map(|source| (quote!(Self), source))
+            .into_iter()
+            .collect(),
+        Data::Enum(data) => {
+            if let Some(attr) = input
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("error"))
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`transparent` is only allowed on variants of enum",
+                ));
+            }
+            let mut sources = vec![];
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                if let Some(source) = Source::find(&variant.fields, &variant.attrs, ident)? {
+                    sources.push((quote!(Self::#ident), source));
+                }
+            }
+            sources
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    // без атрибутов вывода `Display` реализуется пользователем
+    let display = match has_display(input) {
+        true => {
+            let (body, generics) = fmt_body(input, "display", is_transparent)?;
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            Some(quote! {
+                impl #impl_generics ::std::fmt::Display for #ident #ty_generics #where_clause {
+                    fn fmt(&self, __formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        #body
+                    }
+                }
+            })
+        }
+        false => None,
+    };
+
+    let mut generics = input.generics.clone();
+    if generics.type_params().next().is_some() {
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ident #ty_generics: ::std::fmt::Debug + ::std::fmt::Display));
+    }
+    for (_, Source { ty, .. }) in &sources {
+        if uses_type_params(ty, &input.generics) {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::std::error::Error + 'static));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let as_dyn_error = (!sources.is_empty()).then(as_dyn_error);
+
+    let arms = sources
+        .iter()
+        .map(|(path, source)| source.arm(path.clone()));
+    let froms = sources
+        .iter()
+        .filter_map(|(path, source)| source.from(input, path.clone()));
+
+    Ok(quote! {
+        #display
+
+        impl #impl_generics ::std::error::Error for #ident #ty_generics #where_clause {
+            fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                #as_dyn_error
+
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        #(#froms)*
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,196 @@
+use macro_functions::{Case, FieldOptions};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, Data, DataEnum, DataStruct, DataUnion,
+    DeriveInput, Field, Ident, Member, WherePredicate,
+};
+
+use crate::setter_getter::select;
+
+/// Имя варианта перечисления полей: `font_size` -> `FontSize`, `0` -> `Field0`.
+fn variant_ident(name: &str, options: &FieldOptions, field: &Field) -> syn::Result<Ident> {
+    let variant = match name.starts_with(|ch: char| ch.is_ascii_digit()) {
+        true => format!("Field{name}"),
+        false => Case::Pascal.convert(name),
+    };
+    syn::parse_str(&variant).map_err(|_| {
+        let message = format!("`{variant}` is not a valid variant name");
+        match &options.rename {
+            Some(rename) => syn::Error::new_spanned(rename, message),
+            None => syn::Error::new_spanned(field, message),
+        }
+    })
+}
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                enum_token,
+                "enums are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+
+    let mut items = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let options = FieldOptions::parse(&field.attrs)?;
+        if !options.skip {
+            items.push((&field.attrs[..], (i, field, options)));
+        }
+    }
+    let mut variants: Vec<Ident> = vec![];
+    let mut members = vec![];
+    let mut names: Vec<String> = vec![];
+    let mut tys = vec![];
+    for (i, field, options) in select(items, "fields") {
+        let (member, name) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.unraw().to_string()),
+            None => (Member::from(i), i.to_string()),
+        };
+        let name = options.name(name);
+        let variant = variant_ident(&name, &options, field)?;
+        // разные имена могут дать один вариант: `foo_bar` и `fooBar` -> `FooBar`
+        let conflict = match names.contains(&name) {
+            true => Some(format!("field name `{name}` is already used")),
+            false => variants
+                .iter()
+                .position(|other| *other == variant)
+                .map(|j| {
+                    let other = &names[j];
+                    format!("fields `{other}` and `{name}` both map to variant `{variant}`")
+                }),
+        };
+        if let Some(message) = conflict {
+            return Err(match &options.rename {
+                Some(rename) => syn::Error::new_spanned(rename, message),
+                None => syn::Error::new_spanned(field, message),
+            });
+        }
+        variants.push(variant);
+        members.push(member);
+        names.push(name);
+        tys.push(&field.ty);
+    }
+
+    let field_enum = format_ident!("{ident}Field");
+    let enum_doc = format!(" Поля [`{ident}`].");
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut any_generics = generics.clone();
+    if !generics.params.is_empty() {
+        let predicates = &mut any_generics.make_where_clause().predicates;
+        for ty in &tys {
+            let predicate: WherePredicate = parse_quote!(#ty: ::std::any::Any);
+            if !predicates.iter().any(|p| p == &predicate) {
+                predicates.push(predicate);
+            }
+        }
+    }
+    let (_, _, any_where_clause) = any_generics.split_for_impl();
+
+    let visitor = quote!(::std_reset::traits::fields::Visitor);
+    let visitor_mut = quote!(::std_reset::traits::fields::VisitorMut);
+
+    Ok(quote! {
+        #[doc = #enum_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #field_enum {
+            #(#variants,)*
+        }
+
+        impl #field_enum {
+            /// Все поля в порядке объявления.
+            pub const ALL: &'static [Self] = &[#(Self::#variants),*];
+
+            /// Имя поля.
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                }
+            }
+
+            /// Поле с именем `name`.
+            pub fn from_name(name: &str) -> ::std::option::Option<Self> {
+                match name {
+                    #(#names => ::std::option::Option::Some(Self::#variants),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Имена полей в порядке объявления.
+            pub const FIELD_NAMES: &'static [&'static str] = &[#(#names),*];
+        }
+
+        impl #impl_generics #ident #ty_generics #any_where_clause {
+            /// Ссылка на поле с именем `name`.
+            pub fn get_dyn(&self, name: &str) -> ::std::option::Option<&dyn ::std::any::Any> {
+                match name {
+                    #(#names => ::std::option::Option::Some(&self.#members),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            /// Изменяемая ссылка на поле с именем `name`.
+            pub fn get_dyn_mut(&mut self, name: &str) -> ::std::option::Option<&mut dyn ::std::any::Any> {
+                match name {
+                    #(#names => ::std::option::Option::Some(&mut self.#members),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            /// Записывает `value` в поле с именем `name`.
+            ///
+            /// Если поля нет или тип значения не совпадает с типом поля, `value` возвращается обратно.
+            pub fn set_dyn(
+                &mut self,
+                name: &str,
+                value: ::std::boxed::Box<dyn ::std::any::Any>,
+            ) -> ::std::result::Result<(), ::std::boxed::Box<dyn ::std::any::Any>> {
+                match name {
+                    #(#names => {
+                        self.#members = *value.downcast::<#tys>()?;
+                        ::std::result::Result::Ok(())
+                    })*
+                    _ => ::std::result::Result::Err(value),
+                }
+            }
+
+            /// Передаёт `visitor` имя и ссылку на каждое поле.
+            pub fn visit(&self, visitor: &mut impl #visitor) {
+                #(#visitor::visit(visitor, #names, &self.#members);)*
+            }
+
+            /// Передаёт `visitor` имя и изменяемую ссылку на каждое поле.
+            pub fn visit_mut(&mut self, visitor: &mut impl #visitor_mut) {
+                #(#visitor_mut::visit_mut(visitor, #names, &mut self.#members);)*
+            }
+        }
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,76 @@
+use macro_functions::FieldOptions;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_macro_input, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Member,
+};
+
+use crate::setter_getter::select;
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                enum_token,
+                "enums are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+
+    let mut items = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let options = FieldOptions::parse(&field.attrs)?;
+        if !options.skip {
+            items.push((&field.attrs[..], (i, field, options)));
+        }
+    }
+    let mut lenses = vec![];
+    for (i, field, options) in select(items, "lens") {
+        let (member, name) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.unraw().to_string()),
+            None => (Member::from(i), i.to_string()),
+        };
+        let ty = &field.ty;
+        let vis = options.vis.as_ref().unwrap_or(&field.vis);
+        let doc = format!(" Линза на поле `{name}`.");
+        let method = options.method_ident("lens_", name)?;
+        lenses.push(quote! {
+            #[doc = #doc]
+            #vis fn #method() -> ::std_reset::traits::lens::FieldLens<Self, #ty> {
+                ::std_reset::traits::lens::FieldLens::new(
+                    |source| &source.#member,
+                    |source| &mut source.#member,
+                )
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#lenses)*
+        }
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -1,3 +1,49 @@
+//! # Общие настройки полей
+//!
+//! Кроме собственных атрибутов, все derive-макросы понимают атрибут поля `#[std_reset(...)]`:
+//! - `skip` - макрос не обрабатывает поле;
+//! - `rename = "..."` - имя поля в генерируемых методах и строках;
+//! - `vis = "..."` - видимость генерируемых методов;
+//! - `into` - методы принимают `impl Into<T>`;
+//! - `default = expr` - значение поля по умолчанию, то же что `#[default(expr)]`.
+//!
+//! Каждый макрос использует подходящие ему настройки:
+//! - [`Setter`](macro@Setter), [`Getter`](macro@Getter) и [`Accessors`](macro@Accessors) - `skip`, `rename`, `vis` и `into`;
+//! - [`New`](macro@New) - `skip` (поле получает значение по умолчанию), `rename` (имя аргумента) и `into`;
+//! - [`Default`](macro@Default) - `default`;
+//! - [`Reset`](macro@Reset) - `skip`, `rename` и `default`;
+//! - [`Patch`](macro@Patch) - `skip`;
+//! - [`Fields`](macro@Fields) - `skip` и `rename`;
+//! - [`Lens`](macro@Lens) - `skip`, `rename` и `vis`;
+//! - [`EnumUtils`](macro@EnumUtils) - `rename` варианта;
+//! - [`Display`](macro@Display) и [`Error`](macro@Error) - `skip` и `rename` в выводе без строки формата;
+//! - [`Preset`](macro@Preset) - `skip` (поле нельзя задать в предустановке), `rename` (ключ поля), `into` и `default`.
+//! - [`Soa`](macro@Soa) - `rename` и `vis` методов столбцов.
+//!
+//! Настройки, которые к макросу не относятся (например, `vis` для [`Display`](macro@Display)),
+//! остаются для других макросов структуры. Настройки, которые противоречат коду макроса,
+//! приводят к ошибке компиляции: `skip` для поля разыменования в [`Deref`](macro@Deref) и [`Newtype`](macro@Newtype),
+//! для полей [`Ops`](macro@Ops) и [`Soa`](macro@Soa) и для вариантов [`EnumUtils`](macro@EnumUtils) и [`AsPrim`](macro@AsPrim).
+//!
+//! Прежние атрибуты (`exclude_setter`, `#[setter(into)]`, `#[default(...)]` и т.д.) продолжают работать
+//! и имеют приоритет над `#[std_reset(...)]`.
+//! ```
+//! use std_reset_macros::{Accessors, Default, New};
+//!
+//! #[derive(Accessors, New, Default, Debug)]
+//! struct User {
+//!     #[std_reset(into, rename = "login")]
+//!     name: String,
+//!     #[std_reset(skip, default = 18)]
+//!     age: u8,
+//! }
+//!
+//! let user = User::new("ferris").set_login("crab");
+//! assert_eq!(user.get_login(), "crab");
+//! assert_eq!(user.age, 18);
+//! assert_eq!(User::default().age, 18);
+//! ```
+
 #![allow(unused)]
 
 use macro_functions::{get_segment_from_type, type_from_args};
@@ -146,15 +192,534 @@ use syn::{parse_macro_input, Field, Fields, FieldsNamed, FieldsUnnamed, ItemStru
 /// #[derive(PartialEq, Debug)]
 /// struct UnnamedStruct;
 /// ```
-#[proc_macro_derive(Default, attributes(default))]
+///
+/// ### 3. Функции-фабрики
+/// Вместо выражения можно указать путь к функции без аргументов, которая вернёт значение поля:
+/// ```
+/// # use std_reset_macros::Default;
+/// #
+/// fn hosts() -> Vec<String> {
+///     vec!["localhost".to_string()]
+/// }
+///
+/// #[derive(Default, PartialEq, Debug)]
+/// struct Config {
+///     #[default(with = hosts)]
+///     hosts: Vec<String>,
+/// }
+/// assert_eq!(Config::default().hosts, ["localhost"]);
+/// ```
+///
+/// ### 4. Константное значение
+/// Атрибут `#[default(const)]` на структуре или перечислении дополнительно создаёт константу `DEFAULT`,
+/// через которую реализуется [`Default`]. Для этого каждое поле должно иметь явное значение по умолчанию,
+/// вычислимое в константном контексте, а функции из `with` должны быть `const fn`.
+/// ```
+/// # use std_reset_macros::Default;
+/// #
+/// #[derive(Default, PartialEq, Debug)]
+/// #[default(const)]
+/// struct Config {
+///     #[default("localhost")]
+///     host: &'static str,
+///     #[default(8080)]
+///     port: u16,
+/// }
+///
+/// static CONFIG: Config = Config::DEFAULT;
+/// assert_eq!(CONFIG, Config::default());
+/// ```
+///
+/// ### 5. Изменение части полей
+/// Метод `default_with` возвращает значение по умолчанию, в котором замыкание изменило нужные поля:
+/// ```
+/// # use std_reset_macros::Default;
+/// #
+/// #[derive(Default, PartialEq, Debug)]
+/// struct Config {
+///     #[default("localhost")]
+///     host: &'static str,
+///     #[default(8080)]
+///     port: u16,
+/// }
+///
+/// let config = Config::default_with(|config| config.port = 3000);
+/// assert_eq!(config, Config { host: "localhost", port: 3000 });
+/// ```
+///
+/// ### 6. Обобщённые типы
+/// Ограничение `Default` добавляется не на параметры типа, а на типы тех полей,
+/// которые зависят от параметров и не имеют явного значения по умолчанию.
+/// Поля с `PhantomData` ограничений не требуют.
+/// ```
+/// # use std_reset_macros::Default;
+/// # use std::marker::PhantomData;
+/// #
+/// struct NoDefault;
+///
+/// #[derive(Default)]
+/// struct Items<T> {
+///     #[default(Vec::new())]
+///     items: Vec<T>,
+///     marker: PhantomData<T>,
+/// }
+/// assert!(Items::<NoDefault>::default().items.is_empty());
+/// ```
+/// Вычисленные ограничения можно заменить своими с помощью `#[default(bound = "...")]`:
+/// ```
+/// # use std_reset_macros::Default;
+/// #
+/// trait Factory {
+///     fn create() -> Self;
+/// }
+///
+/// impl Factory for i32 {
+///     fn create() -> Self {
+///         7
+///     }
+/// }
+///
+/// #[derive(Default, PartialEq, Debug)]
+/// #[default(bound = "T: Factory")]
+/// struct Value<T>(#[default(T::create())] T);
+///
+/// assert_eq!(Value::<i32>::default(), Value(7));
+/// ```
+///
+/// ### 7. Переменные окружения и разбор строк
+/// `#[default(env = "VAR")]` разбирает значение переменной окружения через [`FromStr`](std::str::FromStr)
+/// при каждом вызове `default()`. Если переменная не задана, используется значение из `fallback`.
+/// `#[default(parse = "...")]` разбирает строковый литерал тем же способом.
+///
+/// При ошибке `default()` паникует с сообщением, в котором указаны поле и переменная,
+/// а метод `try_default()`, создаваемый для таких типов, возвращает эту ошибку.
+/// ```
+/// # use std_reset_macros::Default;
+/// use std::net::Ipv4Addr;
+///
+/// #[derive(Default, PartialEq, Debug)]
+/// struct Config {
+///     #[default(env = "APP_PORT", fallback = 8080)]
+///     port: u16,
+///     #[default(parse = "127.0.0.1")]
+///     ip: Ipv4Addr,
+/// }
+///
+/// assert_eq!(Config::default(), Config { port: 8080, ip: Ipv4Addr::LOCALHOST });
+///
+/// std::env::set_var("APP_PORT", "port");
+/// assert!(Config::try_default()
+///     .unwrap_err()
+///     .to_string()
+///     .starts_with("failed to parse `Config.port` from environment variable `APP_PORT`"));
+/// ```
+#[proc_macro_derive(Default, attributes(default, std_reset))]
 pub fn default_macro_derive(input: TokenStream) -> TokenStream {
     default::expand(input)
 }
 mod default;
 
-/// Автореализация [`Deref`] и [`DerefMut`](https://doc.rust-lang.org/std/ops/trait.DerefMut.html) для структур.
+/// Возврат полей структуры к значениям по умолчанию во время выполнения.
+///
+/// Значения берутся из тех же атрибутов `#[default(...)]`, что и в макросе [`Default`](macro@Default).
+/// Макрос создаёт методы:
+/// - `reset(&mut self)` - возвращает значения по умолчанию всем полям;
+/// - `reset_<поле>(&mut self)` - возвращает значение по умолчанию одному полю;
+/// - `is_default_<поле>(&self) -> bool` - проверяет, равно ли поле значению по умолчанию;
+///   метод доступен, только если тип поля реализует [`PartialEq`].
+///
+/// Для полей кортежных структур вместо имени используется номер: `reset_0`, `is_default_0`.
+/// Поля с `#[std_reset(skip)]` не сбрасываются, а `#[std_reset(rename = "...")]` меняет имя в методах.
+/// ```
+/// use std_reset_macros::{Default, Reset};
+///
+/// #[derive(Default, Reset, Debug, PartialEq)]
+/// struct Settings {
+///     #[default(14)]
+///     font_size: u8,
+///     #[default("dark".to_string())]
+///     theme: String,
+///     zoom: f32,
+/// }
+///
+/// let mut settings = Settings { font_size: 20, theme: "light".to_string(), zoom: 1.5 };
+/// assert!(!settings.is_default_font_size());
+///
+/// settings.reset_font_size();
+/// assert_eq!(settings.font_size, 14);
+/// assert!(settings.is_default_font_size());
+/// assert!(!settings.is_default_theme());
+///
+/// settings.reset();
+/// assert_eq!(settings, Settings::default());
+/// ```
+#[proc_macro_derive(Reset, attributes(default, std_reset))]
+pub fn reset(input: TokenStream) -> TokenStream {
+    reset::expand(input)
+}
+mod reset;
+
+/// Именованные конструкторы из предустановок.
+///
+/// Каждый атрибут `#[preset(name = "...", <поле> = <выражение>, ...)]` структуры создаёт
+/// функцию `<name>() -> Self`. Перечисленные поля получают указанные значения,
+/// остальные - значения по умолчанию из тех же атрибутов, что и в макросе [`Default`](macro@Default):
+/// `#[default(...)]`, `#[std_reset(default = ...)]`, иначе [`Default::default`].
+///
+/// Поддерживаются только структуры с именованными полями.
+/// Имя функции можно задать и как `fn = "..."`, тогда `name` - обычное поле:
+/// `#[preset(fn = "named", name = "...")]`. Без `fn` поле с именем `name` задаётся как `r#name`.
+/// Повторное указание `name` или `fn` в одном атрибуте - ошибка компиляции.
+/// ```
+/// use std_reset_macros::{Default, Preset};
+///
+/// #[derive(Default, Preset, Debug, PartialEq)]
+/// #[preset(name = "dev", port = 8080, debug = true)]
+/// #[preset(name = "prod", host = "example.com".to_string())]
+/// struct Config {
+///     #[default("localhost".to_string())]
+///     host: String,
+///     #[default(80)]
+///     port: u16,
+///     debug: bool,
+/// }
+///
+/// let dev = Config::dev();
+/// assert_eq!(dev.host, "localhost");
+/// assert_eq!(dev.port, 8080);
+/// assert!(dev.debug);
+///
+/// let prod = Config::prod();
+/// assert_eq!(prod.host, "example.com");
+/// assert_eq!(prod.port, 80);
+/// assert!(!prod.debug);
+/// ```
+#[proc_macro_derive(Preset, attributes(preset, default, std_reset))]
+pub fn preset(input: TokenStream) -> TokenStream {
+    preset::expand(input)
+}
+mod preset;
+
+/// Вычисление и применение изменений структуры.
+///
+/// Макрос создаёт структуру `<Имя>Patch` с той же видимостью, в которой каждое поле обёрнуто в [`Option`],
+/// а также методы:
+/// - `diff(&self, other: &Self) -> <Имя>Patch` - изменённые в `other` поля;
+/// - `apply(&mut self, patch: <Имя>Patch)` - переносит изменённые поля;
+/// - `<Имя>Patch::is_empty(&self) -> bool` - нет ни одного изменённого поля.
+///
+/// Для сравнения и копирования полей требуются [`PartialEq`] и [`Clone`].
+/// ```
+/// use std_reset_macros::Patch;
+///
+/// #[derive(Patch, Debug, Clone, PartialEq)]
+/// #[patch(derive(Debug, PartialEq))]
+/// struct State {
+///     name: String,
+///     count: u32,
+/// }
 ///
-/// Макрос поддерживает работу с именованными и неименнованными структурами.
+/// let old = State { name: "crab".to_string(), count: 1 };
+/// let new = State { name: "crab".to_string(), count: 2 };
+///
+/// let patch = old.diff(&new);
+/// assert_eq!(patch, StatePatch { name: None, count: Some(2) });
+/// assert!(old.diff(&old).is_empty());
+///
+/// let mut state = old.clone();
+/// state.apply(patch);
+/// assert_eq!(state, new);
+/// ```
+/// Трейты для `<Имя>Patch` перечисляются в `#[patch(derive(...))]`, [`Default`] реализуется всегда.
+///
+/// ## Отбор полей
+/// Поля отбираются так же, как в [`Setter`](macro@Setter): атрибутами `#[include_patch]` и `#[exclude_patch]`.
+/// Если параметры структуры встречаются только в пропущенных полях, патч получает скрытое поле
+/// `__phantom` с [`PhantomData`](std::marker::PhantomData), поэтому такой патч удобнее создавать с `..Default::default()`.
+/// ```
+/// # use std_reset_macros::Patch;
+/// #[derive(Patch)]
+/// struct State {
+///     count: u32,
+///     #[exclude_patch]
+///     cache: Vec<u8>,
+/// }
+///
+/// let patch = StatePatch { count: Some(1) };
+/// ```
+///
+/// ## Вложенные структуры
+/// Поле с `#[patch(nested)]` хранит в патче не значение, а патч своего типа,
+/// который также должен реализовывать `Patch`.
+/// ```
+/// # use std_reset_macros::Patch;
+/// #[derive(Patch, Clone, PartialEq, Debug)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// #[derive(Patch, Clone, PartialEq, Debug)]
+/// struct Shape {
+///     #[patch(nested)]
+///     center: Point,
+///     radius: u32,
+/// }
+///
+/// let mut shape = Shape { center: Point { x: 0, y: 0 }, radius: 1 };
+/// let moved = Shape { center: Point { x: 0, y: 5 }, radius: 1 };
+///
+/// let patch = shape.diff(&moved);
+/// assert_eq!(patch.center.as_ref().unwrap().y, Some(5));
+/// assert!(patch.center.as_ref().unwrap().x.is_none());
+///
+/// shape.apply(patch);
+/// assert_eq!(shape, moved);
+/// ```
+#[proc_macro_derive(Patch, attributes(patch, include_patch, exclude_patch, std_reset))]
+pub fn patch(input: TokenStream) -> TokenStream {
+    patch::expand(input)
+}
+mod patch;
+
+/// Преобразование массива структур в структуру массивов.
+///
+/// Макрос создаёт структуру `<Имя>Vec` с той же видимостью, в которой каждое поле хранится
+/// в отдельном [`Vec`], и структуру `<Имя>Ref<'_>` со ссылками на поля одного элемента.
+/// Методы `<Имя>Vec`:
+/// - `new() -> Self` и `with_capacity(capacity: usize) -> Self` - пустые столбцы;
+/// - `push(&mut self, value: <Имя>)` - добавляет элемент в конец;
+/// - `get(&self, index: usize) -> Option<<Имя>Ref<'_>>` - ссылки на поля элемента;
+/// - `iter(&self) -> impl Iterator<Item = <Имя>Ref<'_>>` - ссылки на поля всех элементов;
+/// - `len(&self) -> usize` и `is_empty(&self) -> bool`;
+/// - `swap_remove(&mut self, index: usize) -> <Имя>` - удаляет элемент, заменяя его последним;
+/// - `<поле>(&self) -> &[T]` и `<поле>_mut(&mut self) -> &mut [T]` - столбец поля в виде среза
+///   (для кортежных структур `column_0`, `column_1`, ...).
+///
+/// Сами столбцы закрыты, поэтому их длины всегда совпадают. Методы столбцов получают видимость поля
+/// исходной структуры, а `#[std_reset(rename = "...", vis = "...")]` меняет их имя и видимость.
+/// Имя столбца не может совпадать с другими методами `<Имя>Vec`.
+/// Преобразования между `Vec<<Имя>>` и `<Имя>Vec` реализуются трейтом `Of`, поэтому работает `to`.
+/// ```
+/// use std_reset::prelude::{Soa, To};
+///
+/// #[derive(Soa, Debug, Clone, PartialEq)]
+/// #[soa(derive(Debug))]
+/// struct Particle {
+///     x: f32,
+///     y: f32,
+///     mass: u32,
+/// }
+///
+/// let particles = vec![
+///     Particle { x: 0.0, y: 1.0, mass: 2 },
+///     Particle { x: 3.0, y: 4.0, mass: 5 },
+/// ];
+///
+/// let mut soa = particles.clone().to::<ParticleVec>();
+/// assert_eq!(soa.len(), 2);
+/// assert_eq!(soa.x(), [0.0, 3.0]);
+/// soa.mass_mut()[1] += 1;
+/// assert_eq!(*soa.get(1).unwrap().mass, 6);
+/// assert_eq!(soa.iter().map(|particle| *particle.mass).sum::<u32>(), 8);
+///
+/// soa.push(Particle { x: 6.0, y: 7.0, mass: 8 });
+/// let removed = soa.swap_remove(0);
+/// assert_eq!(removed, particles[0]);
+///
+/// let particles = soa.to::<Vec<Particle>>();
+/// assert_eq!(particles[0].mass, 8);
+/// assert_eq!(particles[1].mass, 6);
+/// ```
+/// Трейты для `<Имя>Vec` перечисляются в `#[soa(derive(...))]`, как в [`Patch`](macro@Patch).
+/// `<Имя>Ref` реализует только [`Clone`] и [`Copy`].
+#[proc_macro_derive(Soa, attributes(soa, std_reset))]
+pub fn soa(input: TokenStream) -> TokenStream {
+    soa::expand(input)
+}
+mod soa;
+
+/// Доступ к полям структуры по имени во время выполнения.
+///
+/// Макрос создаёт:
+/// - константу `FIELD_NAMES` с именами полей в порядке объявления;
+/// - перечисление `<Имя>Field` с вариантом на каждое поле (`font_size` -> `FontSize`, `0` -> `Field0`),
+///   константой `ALL` и методами `name` и `from_name`;
+/// - методы `get_dyn`, `get_dyn_mut` и `set_dyn` для чтения и записи поля через [`Any`](std::any::Any);
+/// - методы `visit` и `visit_mut` для обхода полей посетителем из `std_reset::traits::fields`.
+///
+/// Для полей кортежных структур вместо имени используется номер: `"0"`, `"1"`, ...
+/// Имя поля и вариант `<Имя>Field` учитывают `#[std_reset(rename = "...")]`,
+/// а поля отбираются так же, как в [`Setter`](macro@Setter): атрибутами `#[include_fields]` и `#[exclude_fields]`.
+/// Если два поля получают одно имя или один вариант (например, `foo_bar` и `fooBar`), это ошибка компиляции.
+/// ```
+/// use std_reset::prelude::Fields;
+///
+/// #[derive(Fields)]
+/// struct Settings {
+///     font_size: u8,
+///     theme: String,
+/// }
+///
+/// assert_eq!(Settings::FIELD_NAMES, ["font_size", "theme"]);
+/// assert_eq!(SettingsField::ALL, [SettingsField::FontSize, SettingsField::Theme]);
+/// assert_eq!(SettingsField::from_name("theme"), Some(SettingsField::Theme));
+/// assert_eq!(SettingsField::FontSize.name(), "font_size");
+///
+/// let mut settings = Settings { font_size: 14, theme: "dark".to_string() };
+///
+/// let size = settings.get_dyn("font_size").unwrap();
+/// assert_eq!(size.downcast_ref::<u8>(), Some(&14));
+///
+/// settings.set_dyn("theme", Box::new("light".to_string())).unwrap();
+/// assert_eq!(settings.theme, "light");
+///
+/// // тип значения не совпадает с типом поля
+/// assert!(settings.set_dyn("font_size", Box::new(16_i32)).is_err());
+/// assert!(settings.get_dyn("unknown").is_none());
+/// ```
+#[proc_macro_derive(Fields, attributes(include_fields, exclude_fields, std_reset))]
+pub fn fields(input: TokenStream) -> TokenStream {
+    fields::expand(input)
+}
+mod fields;
+
+/// Вспомогательные константы и методы для перечислений.
+///
+/// Для любого перечисления макрос создаёт:
+/// - константу `COUNT` с количеством вариантов;
+/// - метод `name(&self) -> &'static str` с именем варианта;
+/// - методы `is_<вариант>(&self) -> bool`, где имя варианта записано в `snake_case`;
+/// - для вариантов с данными методы `as_<вариант>(&self)` и `into_<вариант>(self)`,
+///   возвращающие [`Option`] с данными варианта или кортежем, если полей несколько.
+///
+/// Если все варианты без данных, то дополнительно создаются константа `VARIANTS` и метод `iter`
+/// с вариантами в порядке объявления, а также реализация [`FromStr`](std::str::FromStr)
+/// с ошибкой `Parse<Имя>Error`.
+/// ```
+/// use std_reset_macros::EnumUtils;
+///
+/// #[derive(EnumUtils, Debug, PartialEq)]
+/// enum Color {
+///     Red,
+///     DarkBlue,
+/// }
+///
+/// assert_eq!(Color::COUNT, 2);
+/// assert_eq!(Color::VARIANTS, [Color::Red, Color::DarkBlue]);
+/// assert_eq!(Color::DarkBlue.name(), "DarkBlue");
+/// assert!(Color::DarkBlue.is_dark_blue());
+/// assert_eq!("Red".parse::<Color>(), Ok(Color::Red));
+/// assert_eq!("red".parse::<Color>().unwrap_err().to_string(), "unknown variant `red` of `Color`");
+///
+/// let names: Vec<_> = Color::iter().map(Color::name).collect();
+/// assert_eq!(names, ["Red", "DarkBlue"]);
+/// ```
+/// ## Стиль имён
+/// Стиль, в котором `name` и `FromStr` записывают имена вариантов, задаётся атрибутом
+/// `#[enum_utils(case = "...")]`: `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`,
+/// `kebab-case`, `lowercase` или `UPPERCASE`. Имя отдельного варианта задаётся через `rename`.
+/// Два варианта с одинаковым именем после переименования - ошибка компиляции.
+/// ```
+/// # use std_reset_macros::EnumUtils;
+/// #[derive(EnumUtils, Debug, PartialEq)]
+/// #[enum_utils(case = "kebab-case")]
+/// enum Level {
+///     VeryLow,
+///     #[enum_utils(rename = "max")]
+///     Maximum,
+/// }
+///
+/// assert_eq!(Level::VeryLow.name(), "very-low");
+/// assert_eq!("max".parse::<Level>(), Ok(Level::Maximum));
+/// ```
+/// ## Варианты с данными
+/// ```
+/// # use std_reset_macros::EnumUtils;
+/// #[derive(EnumUtils)]
+/// enum Shape {
+///     Circle(f32),
+///     Rect { width: f32, height: f32 },
+///     Empty,
+/// }
+///
+/// let rect = Shape::Rect { width: 2.0, height: 3.0 };
+/// assert!(rect.is_rect());
+/// assert_eq!(rect.as_rect(), Some((&2.0, &3.0)));
+/// assert_eq!(rect.as_circle(), None);
+/// assert_eq!(Shape::Circle(1.0).into_circle(), Some(1.0));
+/// ```
+#[proc_macro_derive(EnumUtils, attributes(enum_utils, std_reset))]
+pub fn enum_utils(input: TokenStream) -> TokenStream {
+    enum_utils::expand(input)
+}
+mod enum_utils;
+
+/// Преобразования между перечислением с `#[repr(...)]` и его дискриминантом.
+///
+/// Макрос реализует для перечисления без данных:
+/// - метод `as_`, который приводит дискриминант к любому числовому типу через
+///   [`FromPrim`](https://docs.rs/std-reset/latest/std_reset/traits/as_prim/trait.FromPrim.html);
+/// - `Of<Перечисление>` для типа из `repr`, поэтому работает `to`;
+/// - [`TryFrom`] из типа `repr` с ошибкой `<Имя>DiscriminantError` для неизвестного дискриминанта.
+///
+/// Сгенерированный код ссылается на трейты `std_reset`, поэтому макрос используется через [`std_reset::prelude`](https://docs.rs/std-reset/latest/std_reset/prelude/index.html).
+/// ```
+/// use std_reset::prelude::{AsPrim, To, TryTo};
+///
+/// #[derive(AsPrim, Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Command {
+///     Ping = 1,
+///     Pong,
+///     Close = 0xFF,
+/// }
+///
+/// assert_eq!(Command::Pong.as_::<u32>(), 2);
+/// assert_eq!(Command::Close.to::<u8>(), 255);
+/// assert_eq!(1_u8.try_to::<Command>(), Ok(Command::Ping));
+/// assert_eq!(Command::try_from(7), Err(CommandDiscriminantError(7)));
+/// ```
+///
+/// ## Ограничения
+/// Метод `as_` - собственный метод перечисления, а трейты `ToPrim`, `FromPrim` и `AsPrim` для него
+/// не реализуются: `ToPrim` требует [`ToString`], а `FromPrim` - преобразования из любого числа без ошибки.
+/// Поэтому перечисление не подходит под ограничения `T: AsPrim` и `T: ToPrim`. В обобщённом коде
+/// используются ограничения на сгенерированные преобразования или дискриминант, приведённый через `as_`:
+/// ```
+/// use std_reset::prelude::{AsPrim, Of, TryTo};
+///
+/// fn encode<T>(value: T) -> u8
+/// where
+///     u8: Of<T>,
+/// {
+///     u8::of(value)
+/// }
+///
+/// fn decode<T: TryFrom<u8>>(byte: u8) -> Option<T> {
+///     byte.try_to().ok()
+/// }
+///
+/// #[derive(AsPrim, Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Command {
+///     Ping = 1,
+/// }
+///
+/// assert_eq!(encode(Command::Ping), 1);
+/// assert_eq!(decode::<Command>(1), Some(Command::Ping));
+/// assert_eq!(Command::Ping.as_::<u16>().as_::<f64>(), 1.0);
+/// ```
+#[proc_macro_derive(AsPrim, attributes(std_reset))]
+pub fn as_prim(input: TokenStream) -> TokenStream {
+    as_prim::expand(input)
+}
+mod as_prim;
+
+/// Автореализация [`Deref`] и [`DerefMut`](https://doc.rust-lang.org/std/ops/trait.DerefMut.html) для структур и перечислений.
+///
+/// Макрос поддерживает работу с именованными и неименнованными структурами,
+/// а также с перечислениями. Для unit-структур и объединений макрос выдаёт ошибку компиляции.
 ///
 /// # Реализация с одним неименованным полем
 /// Дефолтная реализация макроса без дополнительных указаний работает только с одним неименованым полем.
@@ -198,14 +763,148 @@ mod default;
 /// };
 /// assert_eq!(*wrapper, "crab");
 /// ```
-
-#[proc_macro_derive(Deref, attributes(deref))]
+/// # Настройки разыменования
+/// Атрибут `#[deref(...)]` указывается для структуры или для поля:
+/// - `target = Type` - разыменование поля к другому типу, например, `String` к `str`.
+///   Без значения (`target`) тип определяется автоматически: `str` для `String`, `[T]` для `Vec<T>`, `T` для `Box<T>` и т.д.;
+/// - `forward` - разыменование через [`Deref`] самого поля, `Target` становится `<T as Deref>::Target`;
+/// - `readonly` - реализуется только [`Deref`], без `DerefMut`.
+/// ```
+/// # use std_reset_macros::Deref;
+/// #
+/// #[derive(Deref)]
+/// #[deref(target = str, readonly)]
+/// struct Name(String);
+///
+/// let name = Name(String::from("crab"));
+/// assert_eq!(&*name, "crab");
+/// assert!(name.starts_with("cr"));
+/// ```
+/// # Перечисления
+/// Каждый вариант перечисления должен иметь одно поле или поле с атрибутом `#[deref]`,
+/// причём тип этих полей во всех вариантах одинаковый. Разыменование выполняется через `match`.
+/// ```
+/// # use std_reset_macros::Deref;
+/// #
+/// #[derive(Deref)]
+/// enum Id {
+///     User(u64),
+///     Org {
+///         name: String,
+///         #[deref]
+///         id: u64,
+///     },
+/// }
+///
+/// assert_eq!(*Id::User(1), 1);
+/// let mut id = Id::Org { name: String::from("crab"), id: 2 };
+/// *id += 1;
+/// assert_eq!(*id, 3);
+/// ```
+#[proc_macro_derive(Deref, attributes(deref, std_reset))]
 pub fn deref_macro_derive(input: TokenStream) -> TokenStream {
     deref::expand(input)
 }
 mod deref;
 
-/// Автоопределение `set` методов для полей именованых структур.
+/// Делегирование типичных трейтов обертки (_newtype_) ее внутреннему полю.
+///
+/// Поле выбирается так же, как и в [`Deref`]: единственное неименованное поле или поле с атрибутом `#[deref]`.
+/// Для поля с типом `T` реализуются:
+/// - [`AsRef<T>`] и [`AsMut<T>`];
+/// - [`Borrow<T>`](std::borrow::Borrow) и [`BorrowMut<T>`](std::borrow::BorrowMut);
+/// - [`From<T>`] для обертки (только для структур с одним полем);
+/// - `From<Обертка>` для `T` (если `T` - не параметр типа структуры);
+/// - [`IntoIterator`] для обертки и ссылки на нее;
+/// - [`FromIterator`] (только для структур с одним полем).
+///
+/// Итерация реализуется, только если `T` - коллекция стандартной библиотеки
+/// ([`Vec`], `VecDeque`, `LinkedList`, `HashMap`, `HashSet`, `BTreeMap`, `BTreeSet`, `BinaryHeap`).
+/// Для других типов, в том числе для параметров типа, она включается атрибутом `#[newtype(iter)]`.
+///
+/// Реализацию любого трейта можно отключить атрибутом
+/// `#[newtype(skip(as_ref, as_mut, borrow, borrow_mut, from, into, into_iterator, from_iterator))]`.
+///
+/// ## Пример
+/// ```
+/// use std_reset_macros::{Deref, Newtype};
+///
+/// #[derive(Deref, Newtype, Debug, PartialEq)]
+/// #[newtype(skip(borrow_mut))]
+/// struct Ids(Vec<u32>);
+///
+/// let mut ids: Ids = (1..=3).collect();
+/// ids.as_mut().push(4);
+/// assert_eq!(ids, Ids::from(vec![1, 2, 3, 4]));
+///
+/// let sum: u32 = (&ids).into_iter().sum();
+/// assert_eq!(sum, 10);
+///
+/// let inner: Vec<u32> = ids.into();
+/// assert_eq!(inner, vec![1, 2, 3, 4]);
+/// ```
+#[proc_macro_derive(Newtype, attributes(deref, newtype, std_reset))]
+pub fn newtype_macro_derive(input: TokenStream) -> TokenStream {
+    newtype::expand(input)
+}
+mod newtype;
+
+/// Реализация арифметических операторов для числовых оберток и векторов.
+///
+/// Операторы применяются к каждому полю структуры, а поля-массивы обрабатываются поэлементно:
+/// - для обертки с одним полем `Wrapper(a) + Wrapper(b) == Wrapper(a + b)`;
+/// - для массивов и нескольких полей одного типа `T` дополнительно реализуются операции со скаляром `T`:
+///   `Vector([a, b]) * k == Vector([a * k, b * k])`.
+///
+/// Вместе с каждым бинарным оператором реализуется его составное присваивание (`AddAssign` для `Add` и т.д.).
+///
+/// По умолчанию реализуются `Add`, `Sub`, `Mul` и `Div`.
+/// Набор операторов задается атрибутом `#[ops(add, sub, mul, div, rem, neg)]`.
+///
+/// ## Пример
+/// ```
+/// use std_reset_macros::Ops;
+///
+/// #[derive(Ops, Debug, PartialEq, Clone, Copy)]
+/// struct Meters(f64);
+///
+/// #[derive(Ops, Debug, PartialEq)]
+/// #[ops(add, mul, neg)]
+/// struct Vector<T>([T; 2]);
+///
+/// #[derive(Ops, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// assert_eq!(Meters(1.5) + Meters(2.0), Meters(3.5));
+/// assert_eq!(Vector([1, 2]) + Vector([3, 4]), Vector([4, 6]));
+/// assert_eq!(-Vector([1, 2]) * 2, Vector([-2, -4]));
+///
+/// let mut point = Point { x: 1, y: 2 };
+/// point -= Point { x: 1, y: 1 };
+/// assert_eq!(point / 1, Point { x: 0, y: 1 });
+/// ```
+/// # Операнды разных примитивных типов
+/// С атрибутом `#[ops(as_prim)]` операнды структуры с единственным параметром типа `T`
+/// могут иметь разные примитивные типы элементов. Левый операнд преобразуется к типу правого
+/// через [`AsPrim`](https://docs.rs/std-reset/latest/std_reset/traits/as_prim/trait.AsPrim.html):
+/// `Vector<f64> + Vector<i32> -> Vector<i32>`, `Vector<i32> * f64 -> Vector<f64>`.
+/// Для этого необходима зависимость от `std-reset`.
+#[proc_macro_derive(Ops, attributes(ops, std_reset))]
+pub fn ops_macro_derive(input: TokenStream) -> TokenStream {
+    ops::expand(input)
+}
+mod ops;
+
+/// Автоопределение `set` методов для полей структур и перечислений.
+///
+/// Для неименованных полей методы получают имена по индексу поля: `set_0`, `set_1` и т.д.
+/// Для перечислений `set_` методы определяются для каждого поля (по имени или индексу) всех вариантов.
+/// Если поля нет в части вариантов, метод принимает `&mut self` независимо от `mode` и возвращает `bool`:
+/// `true`, если текущий вариант содержит поле и значение записано, иначе `false`.
+/// Настройка `collection` для таких полей недоступна.
 ///
 /// По умолчанию все поля включены в определение `set_` методов.
 /// Также с помощью атрибутов можно опционально исключать полe из определния `set_` метода,
@@ -288,24 +987,318 @@ mod deref;
 /// ```
 /// -- здесь метод `set_` определен только для полей `first` и `third`.
 ///
+/// # Виды `set_` методов
+///
+/// Вид метода задается атрибутом `#[setter(mode = "...")]` для всей структуры или для отдельного поля
+/// (атрибут поля приоритетнее атрибута структуры):
+/// - `owned` (по умолчанию) - `fn set_x(mut self, value: T) -> Self`, цепочка вызовов по значению, не требует [`Clone`];
+/// - `mut_ref` - `fn set_x(&mut self, value: T) -> &mut Self`, цепочка вызовов по изменяемой ссылке;
+/// - `plain` - `fn set_x(&mut self, value: T)`, обычный мутатор.
+/// ## Пример
+/// ```
+/// # use std_reset_macros::Setter;
+/// #[derive(Setter, Default, PartialEq, Debug)]
+/// #[setter(mode = "mut_ref")]
+/// struct Tmp {
+///     first: String,
+///     #[setter(mode = "plain")]
+///     second: Vec<i32>,
+///     #[setter(mode = "owned")]
+///     third: i32,
+/// }
+/// let mut tmp = Tmp::default().set_third(3);
+/// tmp.set_first("crab".to_string()).set_first("ferris".to_string());
+/// tmp.set_second(vec![1, 2]);
+/// assert_eq!(
+///     tmp,
+///     Tmp {
+///         first: "ferris".to_string(),
+///         second: vec![1, 2],
+///         third: 3
+///     }
+/// );
+/// ```
+///
+/// # Имя и видимость методов
+///
+/// Атрибут `#[setter(...)]` также задает имя и видимость метода:
+/// - `prefix = "..."` - префикс имени вместо `set_` (для структуры или поля);
+/// - `name = "..."` - полное имя метода (только для поля);
+/// - `vis = "..."` - видимость метода вместо `pub` (для структуры или поля).
+///
+/// Doc-комментарии поля переносятся на его метод.
+/// ## Пример
+/// ```
+/// # use std_reset_macros::Setter;
+/// #[derive(Setter, Default, PartialEq, Debug)]
+/// #[setter(prefix = "with_")]
+/// struct Tmp {
+///     /// Первое поле.
+///     first: i32,
+///     #[setter(name = "second", vis = "pub(crate)")]
+///     second: i32,
+/// }
+/// assert_eq!(
+///     Tmp::default().with_first(2).second(3),
+///     Tmp {
+///         first: 2,
+///         second: 3
+///     }
+/// );
+/// ```
+///
+/// # Проверка, хуки и преобразование значения
+///
+/// - `#[setter(into)]` - метод принимает `impl Into<T>` (для структуры или поля);
+/// - `#[setter(validate = path)]` - перед присваиванием значение проверяется функцией `fn(&T) -> Result<(), E>`,
+///   а метод возвращает `Result<_, E>`. Тип ошибки задается через `error = Type`,
+///   по умолчанию это `Box<dyn Error + Send + Sync>`. В режиме `owned` метод забирает структуру,
+///   поэтому при ошибке возвращает ее вместе с ошибкой: `Result<Self, (Self, E)>`;
+/// - `#[setter(on_change = Self::method)]` - после присваивания вызывается `fn(&Self, old: &T, new: &T)`.
+/// ## Пример
+/// ```
+/// # use std_reset_macros::Setter;
+/// fn check_port(port: &u16) -> Result<(), String> {
+///     (*port >= 1024).then_some(()).ok_or(format!("port {port} is reserved"))
+/// }
+///
+/// #[derive(Setter, Default, Debug)]
+/// #[setter(into)]
+/// struct Config {
+///     #[setter(validate = check_port, error = String)]
+///     port: u16,
+///     #[setter(on_change = Self::log_host)]
+///     host: String,
+/// }
+///
+/// impl Config {
+///     fn log_host(&self, old: &String, new: &String) {
+///         println!("host: {old} -> {new}");
+///     }
+/// }
+///
+/// let config = Config::default().set_host("localhost").set_port(8080_u16).unwrap();
+/// assert_eq!(config.port, 8080);
+///
+/// let (config, err) = config.set_port(80_u16).unwrap_err();
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(err, "port 80 is reserved");
+/// ```
+///
+/// # Методы коллекций
+///
+/// Атрибут `#[setter(collection)]` поля добавляет к `set_` методу методы для работы с содержимым поля:
+/// - `Vec<T>` - `push_`, `extend_` и `clear_`;
+/// - `HashMap<K, V>` и `BTreeMap<K, V>` - `insert_` и `remove_`;
+/// - `Option<T>` - `take_` и `clear_`.
 ///
+/// `push_`, `extend_`, `insert_` и `clear_` имеют тот же вид, что и `set_` метод поля,
+/// а `take_` и `remove_` принимают `&mut self` и возвращают извлечённое значение.
+/// ## Пример
+/// ```
+/// # use std_reset_macros::Setter;
+/// use std::collections::HashMap;
+///
+/// #[derive(Setter, Default, Debug)]
+/// struct Request {
+///     #[setter(collection)]
+///     headers: Vec<String>,
+///     #[setter(collection)]
+///     query: HashMap<String, String>,
+///     #[setter(collection)]
+///     body: Option<String>,
+/// }
+///
+/// let mut request = Request::default()
+///     .push_headers("Host: localhost".to_string())
+///     .extend_headers(["Accept: */*".to_string()])
+///     .insert_query("page".to_string(), "2".to_string())
+///     .set_body(Some("{}".to_string()));
 ///
-#[proc_macro_derive(Setter, attributes(exclude_setter, include_setter))]
+/// assert_eq!(request.headers.len(), 2);
+/// assert_eq!(request.remove_query("page"), Some("2".to_string()));
+/// assert_eq!(request.take_body(), Some("{}".to_string()));
+/// assert_eq!(request.body, None);
+/// ```
+#[proc_macro_derive(Setter, attributes(exclude_setter, include_setter, setter, std_reset))]
 pub fn setter_macro_derive(input: TokenStream) -> TokenStream {
     setter_getter::expand_setter(input)
 }
 mod setter_getter;
 
-/// Автоопределение `get` методов для полей именованых структур.
+/// Автоопределение `get` методов для полей структур и перечислений.
 /// Тоже самое что и в [`Setter`], но:
 /// - вместо аттрибута `exclude_setter` - `exclude_getter`
 /// - вместо аттрибута `include_setter` - `include_getter`
 /// - вместо `set_` метода - `get_`
-#[proc_macro_derive(Getter, attributes(exclude_getter, include_getter))]
+///
+/// # Виды `get_` методов
+///
+/// По умолчанию `get_` метод возвращает ссылку на поле: `fn get_x(&self) -> &T`.
+/// Вид метода меняется атрибутом `#[getter(...)]` для всей структуры или для отдельного поля:
+/// - `copy` - `fn get_x(&self) -> T` для [`Copy`] полей;
+/// - `clone` - `fn get_x(&self) -> T` c клонированием поля;
+/// - `deref` - `&str` для `String`, `&[T]` для `Vec<T>`, `&T` для `Box<T>`, `Option<&T>` для `Option<T>` и т.д.;
+/// - `mut` - дополнительно определяет метод `fn x_mut(&mut self) -> &mut T`.
+/// ## Пример
+/// ```
+/// use std_reset_macros::Getter;
+///
+/// #[derive(Getter, Default)]
+/// struct Tmp {
+///     #[getter(mut)]
+///     first: Vec<i32>,
+///     #[getter(deref)]
+///     second: String,
+///     #[getter(copy)]
+///     third: i32,
+///     #[getter(clone)]
+///     fourth: String,
+///     #[getter(deref)]
+///     fifth: Option<String>,
+/// }
+/// let mut tmp = Tmp {
+///     second: "crab".to_string(),
+///     ..Default::default()
+/// };
+/// tmp.first_mut().push(1);
+///
+/// assert_eq!(tmp.get_first(), &vec![1]);
+/// assert_eq!(tmp.get_second(), "crab");
+/// assert_eq!(tmp.get_third(), 0);
+/// assert_eq!(tmp.get_fourth(), String::new());
+/// assert_eq!(tmp.get_fifth(), None);
+/// ```
+///
+/// # Имя и видимость методов
+///
+/// Так же как и в [`Setter`], задаются `prefix`, `name` и `vis`.
+/// Например, идиоматичные для Rust геттеры без префикса `get_`:
+/// ```
+/// # use std_reset_macros::Getter;
+/// #[derive(Getter)]
+/// #[getter(prefix = "", vis = "pub(crate)")]
+/// struct Tmp {
+///     first: i32,
+///     #[getter(name = "last", mut)]
+///     second: String,
+/// }
+/// let mut tmp = Tmp {
+///     first: 1,
+///     second: "crab".to_string(),
+/// };
+/// tmp.last_mut().push('s');
+/// assert_eq!(*tmp.first(), 1);
+/// assert_eq!(tmp.last(), "crabs");
+/// ```
+///
+/// # Неименованные поля и перечисления
+///
+/// Для неименованных полей методы получают имена по индексу поля: `get_0`, `get_1` и т.д.
+///
+/// Для перечислений методы определяются для каждого поля (по имени или индексу) всех вариантов:
+/// если поле есть во всех вариантах, метод возвращает `&T`, иначе - `Option<&T>`.
+/// ```
+/// # use std_reset_macros::Getter;
+/// #[derive(Getter)]
+/// #[getter(prefix = "")]
+/// enum Message {
+///     Text { id: u32, body: String },
+///     Ping { id: u32 },
+/// }
+/// let text = Message::Text {
+///     id: 1,
+///     body: "crab".to_string(),
+/// };
+/// assert_eq!(*text.id(), 1);
+/// assert_eq!(text.body(), Some(&"crab".to_string()));
+/// assert_eq!(Message::Ping { id: 2 }.body(), None);
+/// ```
+#[proc_macro_derive(Getter, attributes(exclude_getter, include_getter, getter, std_reset))]
 pub fn getter_macro_derive(input: TokenStream) -> TokenStream {
     setter_getter::expand_getter(input)
 }
 
+/// `get` и `set` методы одним макросом: то же, что [`Getter`](macro@Getter) и [`Setter`](macro@Setter) вместе.
+///
+/// Понимает атрибуты обоих макросов и общий атрибут `#[std_reset(...)]`.
+/// Поля разбираются один раз, поэтому каждая ошибка в атрибутах сообщается один раз.
+/// Конструкторы не создаются: [`New`](macro@New) и [`Default`](macro@Default) выводятся отдельно
+/// и читают тот же атрибут `#[std_reset(...)]`.
+/// ```
+/// use std_reset_macros::Accessors;
+///
+/// #[derive(Accessors, Default)]
+/// #[setter(mode = "mut_ref")]
+/// struct Config {
+///     #[getter(copy)]
+///     port: u16,
+///     #[std_reset(rename = "address", into)]
+///     host: String,
+///     #[std_reset(skip)]
+///     secret: String,
+/// }
+///
+/// let mut config = Config::default();
+/// config.set_port(8080).set_address("localhost");
+/// assert_eq!(config.get_port(), 8080);
+/// assert_eq!(config.get_address(), "localhost");
+/// ```
+#[proc_macro_derive(
+    Accessors,
+    attributes(
+        exclude_getter,
+        include_getter,
+        getter,
+        exclude_setter,
+        include_setter,
+        setter,
+        std_reset
+    )
+)]
+pub fn accessors(input: TokenStream) -> TokenStream {
+    setter_getter::expand_accessors(input)
+}
+
+/// Линзы для полей структуры.
+///
+/// Для каждого поля определяется метод `lens_<поле>`, который возвращает
+/// [`FieldLens`](https://docs.rs/std-reset/latest/std_reset/traits/lens/struct.FieldLens.html)
+/// с видимостью поля. Линза реализует трейт `Lens` с методами:
+/// - `get` - ссылка на поле;
+/// - `set` - значение, в котором поле заменено;
+/// - `modify` - значение, в котором поле заменено результатом функции;
+/// - `then` - линза на поле внутри поля.
+///
+/// Сгенерированный код ссылается на `std_reset`, поэтому макрос используется через [`std_reset::prelude`](https://docs.rs/std-reset/latest/std_reset/prelude/index.html).
+/// ```
+/// use std_reset::prelude::Lens;
+///
+/// #[derive(Lens, Debug, PartialEq)]
+/// struct Point(i32, i32);
+///
+/// #[derive(Lens, Debug, PartialEq)]
+/// struct Shape {
+///     name: &'static str,
+///     center: Point,
+/// }
+///
+/// let shape = Shape { name: "circle", center: Point(0, 0) };
+/// let x = Shape::lens_center().then(Point::lens_0());
+///
+/// let shape = x.modify(shape, |x| x + 10);
+/// let shape = Shape::lens_name().set(shape, "dot");
+/// assert_eq!(shape, Shape { name: "dot", center: Point(10, 0) });
+/// assert_eq!(*x.get(&shape), 10);
+/// ```
+///
+/// Поля отбираются так же, как в [`Setter`](macro@Setter): атрибутами `#[include_lens]` и `#[exclude_lens]`.
+#[proc_macro_derive(Lens, attributes(include_lens, exclude_lens, std_reset))]
+pub fn lens(input: TokenStream) -> TokenStream {
+    lens::expand(input)
+}
+mod lens;
+
 /// Прямая реализация метода `new`.
 ///
 /// Макрос поддерживает работу с именованными и неименованными полями.
@@ -338,17 +1331,96 @@ pub fn getter_macro_derive(input: TokenStream) -> TokenStream {
 ///
 /// Tmp::new(2, 3);
 /// ```
-#[proc_macro_derive(New)]
+///
+/// Атрибут `#[std_reset(...)]` поля настраивает аргументы:
+/// - `skip` - поле не передаётся в `new` и получает значение по умолчанию,
+///   заданное `#[default(...)]` или `#[std_reset(default = ...)]`, иначе [`Default::default`];
+/// - `rename = "..."` - имя аргумента;
+/// - `into` - аргумент принимает `impl Into<T>`.
+/// ```
+/// # use std_reset_macros::New;
+/// #[derive(New)]
+/// struct Connection {
+///     #[std_reset(into, rename = "address")]
+///     host: String,
+///     #[std_reset(skip, default = 3)]
+///     retries: u8,
+///     #[std_reset(skip)]
+///     log: Vec<String>,
+/// }
+///
+/// let connection = Connection::new("localhost");
+/// assert_eq!(connection.host, "localhost");
+/// assert_eq!(connection.retries, 3);
+/// assert!(connection.log.is_empty());
+/// ```
+#[proc_macro_derive(New, attributes(std_reset))]
 pub fn new_macro_derive(input: TokenStream) -> TokenStream {
     new::expand(input)
 }
 mod new;
 
-/// Реализация по умолчанию [`Display`] в качестве [`Debug`](https://doc.rust-lang.org/std/fmt/trait.Debug.html#tymethod.fmt).
+/// Реализация [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html) по строке формата.
 ///
-/// Струкутура, которая реализует [`Debug`] реализует `Display` по умолчанию, выводя строку формата [`debug::fmt`](https://doc.rust-lang.org/std/fmt/trait.Debug.html#tymethod.fmt).
+/// Строка формата задаётся атрибутом `#[display("...")]` на структуре или на каждом варианте перечисления.
+/// Поля доступны в строке формата по имени, поля кортежей - по номеру (`{0}`) или по порядку (`{}`),
+/// спецификаторы формата работают как в [`format!`](https://doc.rust-lang.org/std/macro.format.html).
 ///
-/// ## Пример
+/// Для полей, тип которых зависит от параметров типа, ограничения выводятся автоматически
+/// по спецификатору (`T: Display` для `{0}`, `T: Debug` для `{0:?}`, `T: LowerHex` для `{0:x}` и т.д.).
+/// Поля, упомянутые только в дополнительных аргументах `#[display("...", args)]`, ограничений не получают.
+/// ```
+/// use std_reset_macros::Display;
+///
+/// #[derive(Display)]
+/// #[display("{name} is {age} years old")]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// #[derive(Display)]
+/// #[display("({}, {1:.1})")]
+/// struct Point(i32, f32);
+///
+/// let person = Person { name: "Bob".to_string(), age: 42 };
+/// assert_eq!(person.to_string(), "Bob is 42 years old");
+/// assert_eq!(Point(1, 2.25).to_string(), "(1, 2.2)");
+/// ```
+/// После строки формата можно указать дополнительные аргументы, тогда номера `{0}` и `{}`
+/// ссылаются на них, а не на поля:
+/// ```
+/// # use std_reset_macros::Display;
+/// #[derive(Display)]
+/// #[display("{} items", self.0.len())]
+/// struct Items(Vec<i32>);
+///
+/// assert_eq!(Items(vec![1, 2, 3]).to_string(), "3 items");
+/// ```
+///
+/// ## Перечисления
+/// Вариант с `#[display(skip)]` выводит пустую строку.
+/// ```
+/// # use std_reset_macros::Display;
+/// #[derive(Display, Debug)]
+/// enum Shape {
+///     #[display("circle r={radius}")]
+///     Circle { radius: f64 },
+///     #[display("rect {0}x{1}")]
+///     Rect(u32, u32),
+///     #[display(skip)]
+///     Empty,
+///     Unknown,
+/// }
+///
+/// assert_eq!(Shape::Circle { radius: 1.5 }.to_string(), "circle r=1.5");
+/// assert_eq!(Shape::Rect(2, 3).to_string(), "rect 2x3");
+/// assert_eq!(Shape::Empty.to_string(), "");
+/// assert_eq!(Shape::Unknown.to_string(), "Unknown");
+/// ```
+///
+/// ## Без строки формата
+/// Структура или вариант без атрибута выводится как [`Debug`](https://doc.rust-lang.org/std/fmt/trait.Debug.html#tymethod.fmt).
 /// ```
 /// use std_reset_macros::Display;
 /// use std::fmt::Debug;
@@ -359,12 +1431,95 @@ mod new;
 /// # let exmpl = Exmpl(0);
 /// # assert_eq!(format!("{}", exmpl), format!("{:?}", exmpl));
 /// ```
-#[proc_macro_derive(Display)]
+/// Если часть полей отмечена `#[display(skip)]`, то выводятся только остальные поля
+/// в формате `Debug`, при этом реализация `Debug` для самой структуры не требуется.
+/// Пропущенные поля также недоступны в строке формата.
+/// ```
+/// # use std_reset_macros::Display;
+/// #[derive(Display)]
+/// struct User {
+///     login: String,
+///     #[display(skip)]
+///     password: String,
+/// }
+///
+/// let user = User { login: "admin".to_string(), password: "qwerty".to_string() };
+/// assert_eq!(user.to_string(), r#"User { login: "admin", .. }"#);
+/// ```
+#[proc_macro_derive(Display, attributes(display, std_reset))]
 pub fn display(input: TokenStream) -> TokenStream {
     display::expand(input)
 }
 mod display;
 
+/// Реализация [`Error`](https://doc.rust-lang.org/std/error/trait.Error.html) вместе с [`Display`](https://doc.rust-lang.org/std/fmt/trait.Display.html).
+///
+/// Вывод ошибки задаётся так же, как в макросе [`Display`](macro@Display), атрибутом `#[display("...")]`,
+/// поэтому выводить `Display` отдельно не нужно. Вариант без атрибута выводится как `Debug`.
+/// Если ни у типа, ни у вариантов нет `#[display(...)]` или `#[error(transparent)]`,
+/// `Display` не реализуется: его можно написать вручную или вывести макросом [`Display`](macro@Display).
+///
+/// Поле, отмеченное `#[source]`, возвращается методом [`source`](https://doc.rust-lang.org/std/error/trait.Error.html#method.source).
+/// Источником может быть тип с [`Error`](https://doc.rust-lang.org/std/error/trait.Error.html)
+/// или `Box<dyn Error>`, в том числе с `Send` и `Sync`.
+/// Поле с `#[from]` также является источником, а для его типа реализуется [`From`],
+/// поэтому такое поле должно быть единственным в структуре или варианте.
+/// ```
+/// use std_reset_macros::Error;
+/// use std::error::Error as _;
+///
+/// #[derive(Error, Debug)]
+/// enum ConfigError {
+///     #[display("failed to read config")]
+///     Io(#[from] std::io::Error),
+///     #[display("invalid port {port}")]
+///     Port {
+///         port: String,
+///         #[source]
+///         source: std::num::ParseIntError,
+///     },
+///     #[display("config is empty")]
+///     Empty,
+/// }
+///
+/// let err: ConfigError = std::io::Error::other("not found").into();
+/// assert_eq!(err.to_string(), "failed to read config");
+/// assert_eq!(err.source().unwrap().to_string(), "not found");
+///
+/// let source = "x".parse::<u16>().unwrap_err();
+/// let err = ConfigError::Port { port: "x".to_string(), source };
+/// assert_eq!(err.to_string(), "invalid port x");
+/// assert!(err.source().is_some());
+///
+/// assert!(ConfigError::Empty.source().is_none());
+/// ```
+///
+/// ## Прозрачные ошибки
+/// Структура или вариант с `#[error(transparent)]` выводит и возвращает источник
+/// своего единственного поля без изменений:
+/// ```
+/// # use std_reset_macros::Error;
+/// use std::error::Error as _;
+///
+/// #[derive(Error, Debug)]
+/// enum AppError {
+///     #[error(transparent)]
+///     Parse(#[from] std::num::ParseIntError),
+///     #[display("other")]
+///     Other,
+/// }
+///
+/// let inner = "x".parse::<i32>().unwrap_err();
+/// let err = AppError::from(inner.clone());
+/// assert_eq!(err.to_string(), inner.to_string());
+/// assert!(err.source().is_none());
+/// ```
+#[proc_macro_derive(Error, attributes(error, source, from, display, std_reset))]
+pub fn error(input: TokenStream) -> TokenStream {
+    error::expand(input)
+}
+mod error;
+
 #[proc_macro_attribute]
 pub fn any_type(attr: TokenStream, item: TokenStream) -> TokenStream {
     any_type::expand(attr, item)
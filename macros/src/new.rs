@@ -1,65 +1,68 @@
-use std::iter::{Enumerate, Map};
-
 use proc_macro::TokenStream;
-use quote::{format_ident, quote, ToTokens};
-use syn::{parse_macro_input, Fields, FieldsNamed, FieldsUnnamed, ItemStruct};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, parse_macro_input, Ident, ItemStruct, Member};
 
-use macro_functions::fast_impl;
+use macro_functions::{fast_impl, FieldOptions};
 
-pub fn expand(input: TokenStream) -> TokenStream {
-    let struct_ = &parse_macro_input!(input);
+use crate::default::{DefaultValue, OnError};
 
-    let ItemStruct {
-        fields,
-        ident,
-        generics,
-        ..
-    } = struct_;
+fn try_expand(struct_: &ItemStruct) -> syn::Result<TokenStream2> {
+    let ItemStruct { fields, ident, .. } = struct_;
+    let owner = ident.to_string();
 
-    let new = match fields {
-        Fields::Named(FieldsNamed { named, .. }) => {
-            let args = named.iter();
-            let struct_body = named.iter().map(|field| field.ident.clone().unwrap());
-            quote! {
-                pub fn new(#(#args),*) -> Self {
-                    Self {
-                        #(#struct_body),*
-                    }
-                }
-            }
-        }
-        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
-            let (arg, k): (Vec<proc_macro2::TokenStream>, Vec<proc_macro2::Ident>) = unnamed.iter().enumerate().map(|(i, arg)| {
-                let ident = format_ident!("arg_{}", i + 1);
-                (
-                    quote! {
-                        #ident: #arg
-                    },
-                    ident,
-                )
-            }).unzip();
-
-            let body = if unnamed.len() > 1 {
-                quote! {
-                    #(#k),*
-                }
-            } else {
-                quote! {
-                    arg_1
-                }
+    let mut args = vec![];
+    let mut inits = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let options = FieldOptions::parse(&field.attrs)?;
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::from(i),
+        };
+        if options.skip {
+            let name = match &field.ident {
+                Some(ident) => ident.unraw().to_string(),
+                None => i.to_string(),
             };
+            let value =
+                DefaultValue::of_field(field, &format!("{owner}.{name}"), false, OnError::Panic)?;
+            inits.push(quote!(#member: #value));
+            continue;
+        }
+        let arg = match (&options.rename, &field.ident) {
+            (Some(rename), _) => rename.parse::<Ident>()?,
+            (None, Some(ident)) => ident.clone(),
+            (None, None) => format_ident!("arg_{}", i + 1),
+        };
+        if options.is_into {
+            args.push(quote!(#arg: impl ::std::convert::Into<#ty>));
+            inits.push(quote!(#member: ::std::convert::Into::into(#arg)));
+        } else {
+            args.push(quote!(#arg: #ty));
+            inits.push(match &field.ident {
+                Some(ident) if ident == &arg => quote!(#arg),
+                _ => quote!(#member: #arg),
+            });
+        }
+    }
 
-            quote! {
-                pub fn new(#(#arg),*) -> Self {
-                    Self(#body)
+    Ok(fast_impl(
+        struct_,
+        quote! {
+            pub fn new(#(#args),*) -> Self {
+                Self {
+                    #(#inits),*
                 }
             }
-        }
-        _ => {
-            panic!()
-        }
-    };
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        },
+    ))
+}
 
-    fast_impl(struct_, new).into()
+pub fn expand(input: TokenStream) -> TokenStream {
+    let struct_ = parse_macro_input!(input as ItemStruct);
+    match try_expand(&struct_) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
 }
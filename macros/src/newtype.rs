@@ -0,0 +1,239 @@
+use macro_functions::FieldOptions;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Field, Generics, ItemStruct, Type, TypePath};
+
+use crate::deref::deref_field;
+
+/// Трейты, реализацию которых можно отключить через `#[newtype(skip(...))]`.
+const TRAITS: [&str; 8] = [
+    "as_ref",
+    "as_mut",
+    "borrow",
+    "borrow_mut",
+    "from",
+    "into",
+    "into_iterator",
+    "from_iterator",
+];
+
+/// Коллекции стандартной библиотеки, для которых итерация реализуется без `#[newtype(iter)]`.
+const COLLECTIONS: [&str; 8] = [
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "HashMap",
+    "HashSet",
+    "BTreeMap",
+    "BTreeSet",
+    "BinaryHeap",
+];
+
+/// Настройки из атрибута `#[newtype(...)]`.
+#[derive(Default)]
+struct NewtypeConfig {
+    /// `skip(...)`
+    skips: Vec<String>,
+    /// `iter`
+    is_iter: bool,
+}
+
+impl NewtypeConfig {
+    fn parse(struct_: &ItemStruct) -> syn::Result<Self> {
+        let mut config = Self::default();
+        for attr in struct_
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("newtype"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("iter") {
+                    config.is_iter = true;
+                    return Ok(());
+                }
+                if !meta.path.is_ident("skip") {
+                    return Err(meta.error("expected `skip` or `iter`"));
+                }
+                meta.parse_nested_meta(|meta| {
+                    match TRAITS.iter().find(|trait_| meta.path.is_ident(trait_)) {
+                        Some(trait_) => config.skips.push(trait_.to_string()),
+                        None => {
+                            return Err(
+                                meta.error(format!("expected one of: {}", TRAITS.join(", ")))
+                            )
+                        }
+                    }
+                    Ok(())
+                })
+            })?;
+        }
+        Ok(config)
+    }
+}
+
+/// Является ли тип поля коллекцией стандартной библиотеки.
+fn is_collection(ty: &Type) -> bool {
+    let Type::Path(TypePath { path, qself: None }) = ty else {
+        return false;
+    };
+    path.segments
+        .last()
+        .is_some_and(|segment| COLLECTIONS.iter().any(|name| segment.ident == name))
+}
+
+/// Является ли тип поля параметром типа структуры.
+fn is_type_param(ty: &Type, generics: &Generics) -> bool {
+    let Type::Path(TypePath { path, qself: None }) = ty else {
+        return false;
+    };
+    generics
+        .type_params()
+        .any(|param| path.is_ident(&param.ident))
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let struct_: ItemStruct = parse_macro_input!(input);
+    if let Err(err) = FieldOptions::validate_fields(&struct_.fields, "Newtype", &[]) {
+        return err.to_compile_error().into();
+    }
+    let config = match NewtypeConfig::parse(&struct_) {
+        Ok(config) => config,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let is_enabled = |trait_: &str| !config.skips.iter().any(|skip| skip == trait_);
+
+    let ItemStruct {
+        fields,
+        ident,
+        generics,
+        ..
+    } = &struct_;
+    let (field, pos) = match deref_field(fields) {
+        Ok(field) => field,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    if let Err(err) = FieldOptions::parse_checked(&field.attrs, "Newtype", &["skip"]) {
+        return err.to_compile_error().into();
+    }
+    let Field { ty, .. } = &field;
+    let is_single = fields.len() == 1;
+    // для остальных типов итерация включается явно: ограничение `T: IntoIterator` может быть ложным,
+    // а для параметра типа реализация для `&Обертка<T>` рекурсивна и мешает выводу типов
+    let is_iterable = config.is_iter || is_collection(ty);
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut impls = vec![];
+
+    if is_enabled("as_ref") {
+        impls.push(quote! {
+            impl #impl_generics std::convert::AsRef<#ty> for #ident #ty_generics #where_clause {
+                fn as_ref(&self) -> &#ty {
+                    &self.#pos
+                }
+            }
+        });
+    }
+    if is_enabled("as_mut") {
+        impls.push(quote! {
+            impl #impl_generics std::convert::AsMut<#ty> for #ident #ty_generics #where_clause {
+                fn as_mut(&mut self) -> &mut #ty {
+                    &mut self.#pos
+                }
+            }
+        });
+    }
+    if is_enabled("borrow") {
+        impls.push(quote! {
+            impl #impl_generics std::borrow::Borrow<#ty> for #ident #ty_generics #where_clause {
+                fn borrow(&self) -> &#ty {
+                    &self.#pos
+                }
+            }
+        });
+    }
+    if is_enabled("borrow_mut") {
+        impls.push(quote! {
+            impl #impl_generics std::borrow::BorrowMut<#ty> for #ident #ty_generics #where_clause {
+                fn borrow_mut(&mut self) -> &mut #ty {
+                    &mut self.#pos
+                }
+            }
+        });
+    }
+    if is_single && is_enabled("from") {
+        impls.push(quote! {
+            impl #impl_generics std::convert::From<#ty> for #ident #ty_generics #where_clause {
+                fn from(value: #ty) -> Self {
+                    Self { #pos: value }
+                }
+            }
+        });
+    }
+    // `impl<T> From<Wrapper<T>> for T` запрещена правилами согласованности
+    if is_enabled("into") && !is_type_param(ty, generics) {
+        impls.push(quote! {
+            impl #impl_generics std::convert::From<#ident #ty_generics> for #ty #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    value.#pos
+                }
+            }
+        });
+    }
+    if is_iterable && is_enabled("into_iterator") {
+        let mut owned_generics = generics.clone();
+        if !is_collection(ty) {
+            owned_generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: std::iter::IntoIterator));
+        }
+        let (impl_generics, _, where_clause) = owned_generics.split_for_impl();
+        impls.push(quote! {
+            impl #impl_generics std::iter::IntoIterator for #ident #ty_generics #where_clause {
+                type Item = <#ty as std::iter::IntoIterator>::Item;
+                type IntoIter = <#ty as std::iter::IntoIterator>::IntoIter;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    std::iter::IntoIterator::into_iter(self.#pos)
+                }
+            }
+        });
+
+        let mut ref_generics = generics.clone();
+        ref_generics.params.insert(0, parse_quote!('__newtype));
+        ref_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(&'__newtype #ty: std::iter::IntoIterator));
+        let (impl_generics, _, where_clause) = ref_generics.split_for_impl();
+        impls.push(quote! {
+            impl #impl_generics std::iter::IntoIterator for &'__newtype #ident #ty_generics #where_clause {
+                type Item = <&'__newtype #ty as std::iter::IntoIterator>::Item;
+                type IntoIter = <&'__newtype #ty as std::iter::IntoIterator>::IntoIter;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    std::iter::IntoIterator::into_iter(&self.#pos)
+                }
+            }
+        });
+    }
+    if is_iterable && is_single && is_enabled("from_iterator") {
+        let mut item_generics = generics.clone();
+        item_generics.params.push(parse_quote!(__NewtypeItem));
+        item_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: std::iter::FromIterator<__NewtypeItem>));
+        let (impl_generics, _, where_clause) = item_generics.split_for_impl();
+        impls.push(quote! {
+            impl #impl_generics std::iter::FromIterator<__NewtypeItem> for #ident #ty_generics #where_clause {
+                fn from_iter<__NewtypeIter: std::iter::IntoIterator<Item = __NewtypeItem>>(iter: __NewtypeIter) -> Self {
+                    Self { #pos: std::iter::FromIterator::from_iter(iter) }
+                }
+            }
+        });
+    }
+
+    quote!(#(#impls)*).into()
+}
@@ -0,0 +1,479 @@
+use macro_functions::FieldOptions;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DataStruct, DeriveInput, Field, GenericArgument,
+    GenericParam, Generics, Ident, Member, Type, TypeArray, WherePredicate,
+};
+
+/// Бинарные операторы: имя в `#[ops(...)]`, трейт, метод и `*Assign` трейт с методом.
+const BINARY: [(&str, &str, &str, &str, &str); 5] = [
+    ("add", "Add", "add", "AddAssign", "add_assign"),
+    ("sub", "Sub", "sub", "SubAssign", "sub_assign"),
+    ("mul", "Mul", "mul", "MulAssign", "mul_assign"),
+    ("div", "Div", "div", "DivAssign", "div_assign"),
+    ("rem", "Rem", "rem", "RemAssign", "rem_assign"),
+];
+
+/// Операторы по умолчанию, если в `#[ops(...)]` они не перечислены.
+const DEFAULT: [&str; 4] = ["add", "sub", "mul", "div"];
+
+/// Настройки `#[ops(...)]` структуры.
+#[derive(Default)]
+struct OpsConfig {
+    operators: Vec<String>,
+    /// `as_prim` - операнды разных примитивных типов через `AsPrim`.
+    is_as_prim: bool,
+}
+
+impl OpsConfig {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut config = Self::default();
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("ops"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("as_prim") {
+                    config.is_as_prim = true;
+                } else if let Some((name, ..)) =
+                    BINARY.iter().find(|(name, ..)| meta.path.is_ident(name))
+                {
+                    config.operators.push(name.to_string());
+                } else if meta.path.is_ident("neg") {
+                    config.operators.push("neg".to_string());
+                } else {
+                    return Err(
+                        meta.error("expected one of: add, sub, mul, div, rem, neg, as_prim")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        if config.operators.is_empty() {
+            config.operators = DEFAULT.iter().map(|name| name.to_string()).collect();
+        }
+        Ok(config)
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.operators.iter().any(|operator| operator == name)
+    }
+}
+
+/// Поле структуры: значение или массив значений типа `elem`.
+struct OpsField<'a> {
+    member: Member,
+    elem: &'a Type,
+    is_array: bool,
+}
+
+impl<'a> OpsField<'a> {
+    fn new(index: usize, field: &'a Field) -> Self {
+        let Field { ident, ty, .. } = field;
+        let member = match ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        match ty {
+            Type::Array(TypeArray { elem, .. }) => Self {
+                member,
+                elem,
+                is_array: true,
+            },
+            _ => Self {
+                member,
+                elem: ty,
+                is_array: false,
+            },
+        }
+    }
+
+    /// Поэлементное применение `op` к полям `self` и `rhs`, где `rhs` - значение того же типа
+    /// или, если `is_scalar`, одиночное значение.
+    fn binary(
+        &self,
+        is_scalar: bool,
+        op: impl Fn(proc_macro2::TokenStream, proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let member = &self.member;
+        let rhs = if is_scalar {
+            quote!(::std::clone::Clone::clone(&rhs))
+        } else {
+            quote!(rhs.#member)
+        };
+        if self.is_array {
+            let rhs = if is_scalar {
+                rhs
+            } else {
+                quote!(rhs.next().unwrap())
+            };
+            let rhs_iter = (!is_scalar)
+                .then(|| quote!(let mut rhs = ::std::iter::IntoIterator::into_iter(rhs.#member);));
+            let op = op(quote!(lhs.next().unwrap()), rhs);
+            quote! {
+                {
+                    let mut lhs = ::std::iter::IntoIterator::into_iter(self.#member);
+                    #rhs_iter
+                    ::std::array::from_fn(|_| #op)
+                }
+            }
+        } else {
+            op(quote!(self.#member), rhs)
+        }
+    }
+
+    /// Поэлементное применение составного присваивания `op` к полям `self`.
+    fn assign(
+        &self,
+        is_scalar: bool,
+        op: impl Fn(proc_macro2::TokenStream, proc_macro2::TokenStream) -> proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let member = &self.member;
+        match (self.is_array, is_scalar) {
+            (true, true) => {
+                let op = op(quote!(lhs), quote!(::std::clone::Clone::clone(&rhs)));
+                quote! {
+                    for lhs in self.#member.iter_mut() {
+                        #op;
+                    }
+                }
+            }
+            (true, false) => {
+                let op = op(quote!(lhs), quote!(rhs));
+                quote! {
+                    for (lhs, rhs) in self.#member.iter_mut().zip(rhs.#member) {
+                        #op;
+                    }
+                }
+            }
+            (false, true) => {
+                let op = op(
+                    quote!(&mut self.#member),
+                    quote!(::std::clone::Clone::clone(&rhs)),
+                );
+                quote!(#op;)
+            }
+            (false, false) => {
+                let op = op(quote!(&mut self.#member), quote!(rhs.#member));
+                quote!(#op;)
+            }
+        }
+    }
+}
+
+/// Обобщения `generics` с дополнительными условиями `predicates`.
+fn with_predicates(generics: &Generics, predicates: Vec<WherePredicate>) -> Generics {
+    let mut generics = generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_ops(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_ops(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    FieldOptions::validate(&input.data, "Ops", &["skip"])?;
+    let config = OpsConfig::parse(input)?;
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let Data::Struct(DataStruct { fields, .. }) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Ops can only be derived for structs",
+        ));
+    };
+    if fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Ops requires at least one field",
+        ));
+    }
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| OpsField::new(i, field))
+        .collect::<Vec<_>>();
+
+    let mut elems: Vec<&Type> = vec![];
+    fields.iter().for_each(|field| {
+        if !elems.contains(&field.elem) {
+            elems.push(field.elem)
+        }
+    });
+    // Скалярные операции определены, когда все элементы одного типа и их несколько
+    let scalar = (elems.len() == 1 && (fields.len() > 1 || fields[0].is_array)).then(|| elems[0]);
+
+    if config.is_as_prim {
+        return expand_as_prim(input, &config, &fields, &elems, scalar);
+    }
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let members = fields.iter().map(|field| &field.member).collect::<Vec<_>>();
+    let mut impls = vec![];
+
+    for (name, trait_, method, assign_trait, assign_method) in BINARY {
+        if !config.has(name) {
+            continue;
+        }
+        let trait_ = Ident::new(trait_, Span::call_site());
+        let method = Ident::new(method, Span::call_site());
+        let assign_trait = Ident::new(assign_trait, Span::call_site());
+        let assign_method = Ident::new(assign_method, Span::call_site());
+
+        let op = |lhs, rhs| quote!(::std::ops::#trait_::#method(#lhs, #rhs));
+        let assign_op = |lhs, rhs| quote!(::std::ops::#assign_trait::#assign_method(#lhs, #rhs));
+
+        let op_generics = with_predicates(
+            generics,
+            elems
+                .iter()
+                .map(|elem| parse_quote!(#elem: ::std::ops::#trait_<Output = #elem>))
+                .collect(),
+        );
+        let (impl_generics, _, where_clause) = op_generics.split_for_impl();
+        let values = fields.iter().map(|field| field.binary(false, op));
+        impls.push(quote! {
+            impl #impl_generics ::std::ops::#trait_ for #ident #ty_generics #where_clause {
+                type Output = Self;
+
+                fn #method(self, rhs: Self) -> Self {
+                    Self { #(#members: #values),* }
+                }
+            }
+        });
+
+        let assign_generics = with_predicates(
+            generics,
+            elems
+                .iter()
+                .map(|elem| parse_quote!(#elem: ::std::ops::#assign_trait))
+                .collect(),
+        );
+        let (impl_generics, _, where_clause) = assign_generics.split_for_impl();
+        let assigns = fields.iter().map(|field| field.assign(false, assign_op));
+        impls.push(quote! {
+            impl #impl_generics ::std::ops::#assign_trait for #ident #ty_generics #where_clause {
+                fn #assign_method(&mut self, rhs: Self) {
+                    #(#assigns)*
+                }
+            }
+        });
+
+        if let Some(scalar) = scalar {
+            let generics =
+                with_predicates(&op_generics, vec![parse_quote!(#scalar: ::std::clone::Clone)]);
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+            let values = fields.iter().map(|field| field.binary(true, op));
+            impls.push(quote! {
+                impl #impl_generics ::std::ops::#trait_<#scalar> for #ident #ty_generics #where_clause {
+                    type Output = Self;
+
+                    fn #method(self, rhs: #scalar) -> Self {
+                        Self { #(#members: #values),* }
+                    }
+                }
+            });
+
+            let generics = with_predicates(
+                &assign_generics,
+                vec![parse_quote!(#scalar: ::std::clone::Clone)],
+            );
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+            let assigns = fields.iter().map(|field| field.assign(true, assign_op));
+            impls.push(quote! {
+                impl #impl_generics ::std::ops::#assign_trait<#scalar> for #ident #ty_generics #where_clause {
+                    fn #assign_method(&mut self, rhs: #scalar) {
+                        #(#assigns)*
+                    }
+                }
+            });
+        }
+    }
+
+    if config.has("neg") {
+        impls.push(expand_neg(input, &fields, &elems));
+    }
+
+    Ok(quote!(#(#impls)*))
+}
+
+/// Операции с операндами разных примитивных типов: `Vector<F> + Vector<I> -> Vector<I>`,
+/// `Vector<F> * I -> Vector<I>` и `Vector<F> += Vector<I>`.
+/// Структура должна иметь единственный параметр типа, которым типизированы все элементы.
+fn expand_as_prim(
+    input: &DeriveInput,
+    config: &OpsConfig,
+    fields: &[OpsField],
+    elems: &[&Type],
+    scalar: Option<&Type>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let mut type_params = generics.type_params();
+    let (Some(param), None) = (type_params.next(), type_params.next()) else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "`#[ops(as_prim)]` requires exactly one type parameter",
+        ));
+    };
+    let param = &param.ident;
+    if let Some(field) = fields
+        .iter()
+        .find(|field| !matches!(field.elem, Type::Path(path) if path.path.is_ident(param)))
+    {
+        return Err(syn::Error::new_spanned(
+            field.elem,
+            format!("`#[ops(as_prim)]` requires every element to be of type `{param}`"),
+        ));
+    }
+
+    let rhs_param = format_ident!("__OpsRhs");
+    let (_, ty_generics, _) = generics.split_for_impl();
+    // Та же структура, но с типом элементов `__OpsRhs`
+    let rhs_args = generics.params.iter().map(|param_| match param_ {
+        GenericParam::Type(type_param) if &type_param.ident == param => {
+            GenericArgument::Type(parse_quote!(#rhs_param))
+        }
+        GenericParam::Type(type_param) => {
+            let ident = &type_param.ident;
+            GenericArgument::Type(parse_quote!(#ident))
+        }
+        GenericParam::Lifetime(lifetime) => GenericArgument::Lifetime(lifetime.lifetime.clone()),
+        GenericParam::Const(const_param) => {
+            let ident = &const_param.ident;
+            GenericArgument::Const(parse_quote!(#ident))
+        }
+    });
+    let rhs_type = quote!(#ident<#(#rhs_args),*>);
+
+    let mut rhs_generics = generics.clone();
+    rhs_generics.params.push(parse_quote!(#rhs_param));
+    let as_prim = quote!(::std_reset::traits::as_prim::AsPrim);
+    let from_prim = quote!(::std_reset::traits::as_prim::FromPrim);
+
+    let members = fields.iter().map(|field| &field.member).collect::<Vec<_>>();
+    let mut impls = vec![];
+
+    for (name, trait_, method, assign_trait, assign_method) in BINARY {
+        if !config.has(name) {
+            continue;
+        }
+        let trait_ = Ident::new(trait_, Span::call_site());
+        let method = Ident::new(method, Span::call_site());
+        let assign_trait = Ident::new(assign_trait, Span::call_site());
+        let assign_method = Ident::new(assign_method, Span::call_site());
+
+        let op = |lhs, rhs| quote!(::std::ops::#trait_::#method(#as_prim::as_::<#rhs_param>(#lhs), #rhs));
+
+        let generics = with_predicates(
+            &rhs_generics,
+            vec![
+                parse_quote!(#param: #as_prim),
+                parse_quote!(#rhs_param: #from_prim + ::std::ops::#trait_<Output = #rhs_param>),
+            ],
+        );
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let values = fields.iter().map(|field| field.binary(false, op));
+        impls.push(quote! {
+            impl #impl_generics ::std::ops::#trait_<#rhs_type> for #ident #ty_generics #where_clause {
+                type Output = #rhs_type;
+
+                fn #method(self, rhs: #rhs_type) -> #rhs_type {
+                    #ident { #(#members: #values),* }
+                }
+            }
+        });
+
+        if scalar.is_some() {
+            let generics = with_predicates(
+                &generics,
+                vec![parse_quote!(#rhs_param: ::std::clone::Clone)],
+            );
+            let (impl_generics, _, where_clause) = generics.split_for_impl();
+            let values = fields.iter().map(|field| field.binary(true, op));
+            impls.push(quote! {
+                impl #impl_generics ::std::ops::#trait_<#rhs_param> for #ident #ty_generics #where_clause {
+                    type Output = #rhs_type;
+
+                    fn #method(self, rhs: #rhs_param) -> #rhs_type {
+                        #ident { #(#members: #values),* }
+                    }
+                }
+            });
+        }
+
+        let assign_op = |lhs, rhs| quote!(::std::ops::#assign_trait::#assign_method(#lhs, #as_prim::as_::<#param>(#rhs)));
+        let generics = with_predicates(
+            &rhs_generics,
+            vec![
+                parse_quote!(#rhs_param: #as_prim),
+                parse_quote!(#param: #from_prim + ::std::ops::#assign_trait),
+            ],
+        );
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let assigns = fields.iter().map(|field| field.assign(false, assign_op));
+        impls.push(quote! {
+            impl #impl_generics ::std::ops::#assign_trait<#rhs_type> for #ident #ty_generics #where_clause {
+                fn #assign_method(&mut self, rhs: #rhs_type) {
+                    #(#assigns)*
+                }
+            }
+        });
+    }
+
+    if config.has("neg") {
+        impls.push(expand_neg(input, fields, elems));
+    }
+
+    Ok(quote!(#(#impls)*))
+}
+
+fn expand_neg(
+    input: &DeriveInput,
+    fields: &[OpsField],
+    elems: &[&Type],
+) -> proc_macro2::TokenStream {
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let generics = with_predicates(
+        generics,
+        elems
+            .iter()
+            .map(|elem| parse_quote!(#elem: ::std::ops::Neg<Output = #elem>))
+            .collect(),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let members = fields.iter().map(|field| &field.member);
+    let values = fields.iter().map(
+        |OpsField {
+             member, is_array, ..
+         }| {
+            if *is_array {
+                quote!(self.#member.map(::std::ops::Neg::neg))
+            } else {
+                quote!(::std::ops::Neg::neg(self.#member))
+            }
+        },
+    );
+    quote! {
+        impl #impl_generics ::std::ops::Neg for #ident #ty_generics #where_clause {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { #(#members: #values),* }
+            }
+        }
+    }
+}
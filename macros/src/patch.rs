@@ -0,0 +1,306 @@
+use macro_functions::{uses_type_params, FieldOptions};
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Attribute, Data, DataEnum, DataStruct, DataUnion,
+    DeriveInput, Field, Fields, Generics, Member, Path, Token, Type, TypePath, WherePredicate,
+};
+
+use crate::setter_getter::select;
+
+/// Поле, изменения которого переносятся в патч.
+struct PatchField<'a> {
+    /// Поле исходной структуры.
+    member: Member,
+    /// Поле патча.
+    patch_member: Member,
+    field: &'a Field,
+    /// `#[patch(nested)]`
+    is_nested: bool,
+}
+
+impl PatchField<'_> {
+    /// Тип поля в патче без обёртки `Option`.
+    fn patch_ty(&self) -> syn::Result<Type> {
+        let ty = &self.field.ty;
+        if !self.is_nested {
+            return Ok(ty.clone());
+        }
+        let Type::Path(TypePath { qself: None, path }) = ty else {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "`nested` requires a path to a type with `#[derive(Patch)]`",
+            ));
+        };
+        let mut path = path.clone();
+        let segment = path.segments.last_mut().unwrap();
+        segment.ident = format_ident!("{}Patch", segment.ident);
+        Ok(Type::Path(TypePath { qself: None, path }))
+    }
+}
+
+/// Отмечено ли поле `#[patch(nested)]`.
+fn is_nested(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut is_nested = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("patch")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                is_nested = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `nested`"))
+            }
+        })?;
+    }
+    Ok(is_nested)
+}
+
+/// Трейты из `#[<name>(derive(...))]`, которые выводятся для созданной структуры.
+pub fn derives(attrs: &[Attribute], name: &str) -> syn::Result<Vec<Path>> {
+    let mut derives = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident(name)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("derive") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                derives.extend(Punctuated::<Path, Token![,]>::parse_terminated(&content)?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `derive`"))
+            }
+        })?;
+    }
+    Ok(derives)
+}
+
+/// Лайфтаймы и параметры типа, которые не упоминаются в типах полей патча,
+/// в виде типов для `PhantomData`: `&'a ()` и `PhantomData<T>`.
+fn unused_params(generics: &Generics, tys: &[Type]) -> Vec<TokenStream2> {
+    fn collect(tokens: TokenStream2, names: &mut Vec<String>) {
+        let mut is_lifetime = false;
+        for token in tokens {
+            let is_after_quote = std::mem::take(&mut is_lifetime);
+            match token {
+                TokenTree::Group(group) => collect(group.stream(), names),
+                TokenTree::Punct(punct) => is_lifetime = punct.as_char() == '\'',
+                TokenTree::Ident(ident) if is_after_quote => names.push(format!("'{ident}")),
+                TokenTree::Ident(ident) => names.push(ident.to_string()),
+                TokenTree::Literal(_) => {}
+            }
+        }
+    }
+    let mut names = vec![];
+    tys.iter()
+        .for_each(|ty| collect(ty.to_token_stream(), &mut names));
+    let is_unused = |name: String| !names.contains(&name);
+
+    let lifetimes = generics
+        .lifetimes()
+        .map(|param| &param.lifetime)
+        .filter(|lifetime| is_unused(lifetime.to_string()))
+        .map(|lifetime| quote!(&#lifetime ()));
+    let types = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .filter(|ident| is_unused(ident.to_string()))
+        .map(|ident| quote!(::std::marker::PhantomData<#ident>));
+    lifetimes.chain(types).collect()
+}
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                enum_token,
+                "enums are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+
+    let mut items = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        if !FieldOptions::parse(&field.attrs)?.skip {
+            items.push((&field.attrs[..], (i, field)));
+        }
+    }
+    let selected = select(items, "patch");
+    let mut patch_fields = vec![];
+    for (j, (i, field)) in selected.into_iter().enumerate() {
+        let (member, patch_member) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), Member::Named(ident.clone())),
+            None => (Member::from(i), Member::from(j)),
+        };
+        patch_fields.push(PatchField {
+            member,
+            patch_member,
+            field,
+            is_nested: is_nested(&field.attrs)?,
+        });
+    }
+
+    let patch = format_ident!("{ident}Patch");
+    let derives = derives(attrs, "patch")?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let patch_tys = patch_fields
+        .iter()
+        .map(PatchField::patch_ty)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let patch_vis = patch_fields.iter().map(|field| &field.field.vis);
+
+    // параметры, которые встречаются только в пропущенных полях, используются в скрытом поле
+    let unused = unused_params(generics, &patch_tys);
+    let phantom = (!unused.is_empty()).then(|| {
+        let member = match fields {
+            Fields::Named(_) => Member::Named(format_ident!("__phantom")),
+            _ => Member::from(patch_fields.len()),
+        };
+        let ty = quote!(::std::marker::PhantomData<fn() -> (#(#unused,)*)>);
+        (member, ty)
+    });
+    let phantom_init = phantom
+        .as_ref()
+        .map(|(member, _)| quote!(#member: ::std::marker::PhantomData,));
+
+    let definition = match fields {
+        Fields::Named(_) => {
+            let members = patch_fields.iter().map(|field| &field.patch_member);
+            let phantom = phantom
+                .as_ref()
+                .map(|(member, ty)| quote!(#[doc(hidden)] pub #member: #ty,));
+            quote! {
+                {
+                    #(#patch_vis #members: ::std::option::Option<#patch_tys>,)*
+                    #phantom
+                }
+            }
+        }
+        _ => {
+            let phantom = phantom
+                .as_ref()
+                .map(|(_, ty)| quote!(#[doc(hidden)] pub #ty,));
+            quote! {
+                (#(#patch_vis ::std::option::Option<#patch_tys>,)* #phantom) #where_clause;
+            }
+        }
+    };
+    let where_clause_named = match fields {
+        Fields::Named(_) => quote!(#where_clause),
+        _ => TokenStream2::default(),
+    };
+
+    let patch_members: Vec<_> = patch_fields
+        .iter()
+        .map(|field| &field.patch_member)
+        .collect();
+
+    let mut predicates: Vec<WherePredicate> = vec![];
+    let diffs = patch_fields.iter().map(|field| {
+        let PatchField {
+            member,
+            patch_member,
+            is_nested,
+            field: Field { ty, .. },
+        } = field;
+        if !is_nested && uses_type_params(ty, generics) {
+            predicates.push(syn::parse_quote!(#ty: ::std::cmp::PartialEq + ::std::clone::Clone));
+        }
+        let value = match is_nested {
+            true => quote! {{
+                let patch = self.#member.diff(&other.#member);
+                (!patch.is_empty()).then_some(patch)
+            }},
+            false => quote! {
+                (self.#member != other.#member).then(|| ::std::clone::Clone::clone(&other.#member))
+            },
+        };
+        quote!(#patch_member: #value)
+    });
+    let diffs: Vec<_> = diffs.collect();
+    let applies = patch_fields.iter().map(|field| {
+        let PatchField {
+            member,
+            patch_member,
+            is_nested,
+            ..
+        } = field;
+        match is_nested {
+            true => quote! {
+                if let ::std::option::Option::Some(value) = patch.#patch_member {
+                    self.#member.apply(value);
+                }
+            },
+            false => quote! {
+                if let ::std::option::Option::Some(value) = patch.#patch_member {
+                    self.#member = value;
+                }
+            },
+        }
+    });
+
+    let doc = format!(" Изменения полей [`{ident}`].");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(#(#derives),*)]
+        #vis struct #patch #generics #where_clause_named #definition
+
+        impl #impl_generics ::std::default::Default for #patch #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#patch_members: ::std::option::Option::None,)*
+                    #phantom_init
+                }
+            }
+        }
+
+        impl #impl_generics #patch #ty_generics #where_clause {
+            /// Нет ни одного изменённого поля.
+            pub fn is_empty(&self) -> bool {
+                true #(&& self.#patch_members.is_none())*
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Изменения, которые превращают `self` в `other`.
+            pub fn diff(&self, other: &Self) -> #patch #ty_generics
+            where
+                #(#predicates,)*
+            {
+                #patch {
+                    #(#diffs,)*
+                    #phantom_init
+                }
+            }
+
+            /// Применяет изменения из `patch`.
+            pub fn apply(&mut self, patch: #patch #ty_generics) {
+                #(#applies)*
+            }
+        }
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,224 @@
+use macro_functions::FieldOptions;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_macro_input, punctuated::Punctuated, Attribute, Data, DataEnum,
+    DataStruct, DataUnion, DeriveInput, Expr, ExprLit, Fields, Ident, Lit, LitStr, Token,
+};
+
+use crate::default::{DefaultConfig, DefaultValue, OnError};
+
+/// Предустановка из атрибута `#[preset(name = "...", <поле> = <выражение>, ...)]`
+/// или `#[preset(fn = "...", <поле> = <выражение>, ...)]`.
+struct Preset {
+    /// Имя функции.
+    name: Ident,
+    /// Поля и их значения в порядке перечисления.
+    values: Vec<(Ident, Expr)>,
+}
+
+impl Preset {
+    fn parse(attr: &Attribute) -> syn::Result<Self> {
+        let mut func: Option<LitStr> = None;
+        let mut entries: Vec<(Ident, Expr)> = vec![];
+        attr.parse_nested_meta(|meta| {
+            let Some(ident) = meta.path.get_ident() else {
+                return Err(meta.error("expected `fn`, `name` or a field name"));
+            };
+            if ident == "fn" {
+                if func.is_some() {
+                    return Err(meta.error("`fn` is already set"));
+                }
+                func = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+            entries.push((ident.clone(), meta.value()?.parse()?));
+            Ok(())
+        })?;
+
+        // без `fn` ключ `name` задаёт имя функции, а с `fn` - значение поля `name`
+        let lit = match func {
+            Some(lit) => lit,
+            None => {
+                let mut names = entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (ident, _))| ident == "name");
+                let Some((index, _)) = names.next() else {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "expected `name = \"...\"` or `fn = \"...\"`",
+                    ));
+                };
+                if let Some((_, (ident, _))) = names.next() {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "`name` is already set, use `fn = \"...\"` to set the field `name`",
+                    ));
+                }
+                match entries.remove(index) {
+                    (
+                        _,
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit), ..
+                        }),
+                    ) => lit,
+                    (_, value) => {
+                        return Err(syn::Error::new_spanned(value, "expected a function name"))
+                    }
+                }
+            }
+        };
+        let name = lit
+            .parse::<Ident>()
+            .map_err(|_| syn::Error::new_spanned(&lit, "expected a function name"))?;
+
+        let mut values: Vec<(Ident, Expr)> = vec![];
+        for (ident, value) in entries {
+            if values
+                .iter()
+                .any(|(field, _)| field.unraw() == ident.unraw())
+            {
+                return Err(syn::Error::new_spanned(
+                    &ident,
+                    format!("field `{}` is already set", ident.unraw()),
+                ));
+            }
+            values.push((ident, value));
+        }
+        Ok(Self { name, values })
+    }
+}
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                enum_token,
+                "enums are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+    if let Fields::Unnamed(fields) = fields {
+        return Err(syn::Error::new_spanned(
+            fields,
+            "tuple structs are not supported",
+        ));
+    }
+    FieldOptions::validate(data, "Preset", &[])?;
+    let config = DefaultConfig::parse(attrs)?;
+
+    let owner = ident.to_string();
+    // ключ поля в `#[preset(...)]` с учётом `rename` и настройки поля
+    let keys = fields
+        .iter()
+        .map(|field| {
+            let options = FieldOptions::parse(&field.attrs)?;
+            let key = options.name(field.ident.as_ref().unwrap().unraw().to_string());
+            Ok((key, options))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let mut names: Vec<Ident> = vec![];
+    let mut impls = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("preset")) {
+        let Preset { name, values } = Preset::parse(attr)?;
+        if names.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!("preset `{name}` is already defined"),
+            ));
+        }
+        for (field, _) in &values {
+            let key = field.unraw().to_string();
+            match keys.iter().find(|(other, _)| *other == key) {
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        format!("`{ident}` has no field `{key}`"),
+                    ))
+                }
+                Some((_, options)) if options.skip => {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        format!("field `{key}` is skipped and always gets its default value"),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        // ограничения нужны только для полей со значениями по умолчанию
+        let mut rest = Punctuated::<_, Token![,]>::new();
+        let mut inits = vec![];
+        for (field, (key, options)) in fields.iter().zip(&keys) {
+            let field_ident = field.ident.as_ref().unwrap();
+            let value = match values.iter().find(|(other, _)| other.unraw() == key) {
+                Some((_, value)) if options.is_into => quote!(::std::convert::Into::into(#value)),
+                Some((_, value)) => quote!(#value),
+                None => {
+                    rest.push(field.clone());
+                    let name = format!("{owner}.{}", field_ident.unraw());
+                    DefaultValue::of_field(field, &name, false, OnError::Panic)?
+                }
+            };
+            inits.push(quote!(#field_ident: #value));
+        }
+        let rest = Fields::Named(syn::FieldsNamed {
+            brace_token: Default::default(),
+            named: rest,
+        });
+        let preset_generics = config.bounds(generics, &rest)?;
+        let (impl_generics, ty_generics, where_clause) = preset_generics.split_for_impl();
+
+        let set: Vec<_> = values
+            .iter()
+            .map(|(field, _)| format!("`{}`", field.unraw()))
+            .collect();
+        let doc = match set.is_empty() {
+            true => format!(" Предустановка `{name}`: все поля по умолчанию."),
+            false => format!(
+                " Предустановка `{name}`: {}, остальные поля по умолчанию.",
+                set.join(", ")
+            ),
+        };
+        let body = match fields {
+            Fields::Unit => quote!(Self),
+            _ => quote!(Self { #(#inits),* }),
+        };
+        let item = quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #[doc = #doc]
+                pub fn #name() -> Self {
+                    #body
+                }
+            }
+        };
+        impls.push(item);
+        names.push(name);
+    }
+
+    Ok(quote!(#(#impls)*))
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,94 @@
+use macro_functions::FieldOptions;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_macro_input, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Member,
+};
+
+use crate::default::{DefaultConfig, DefaultValue, OnError};
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        attrs,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                enum_token,
+                "enums are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+
+    let config = DefaultConfig::parse(attrs)?;
+    let generics = config.bounds(generics, fields)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let owner = ident.to_string();
+
+    let mut resets = vec![];
+    let mut methods = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let options = FieldOptions::parse(&field.attrs)?;
+        if options.skip {
+            continue;
+        }
+        let ty = &field.ty;
+        let (member, name) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.unraw().to_string()),
+            None => (Member::from(i), i.to_string()),
+        };
+        let value =
+            DefaultValue::of_field(field, &format!("{owner}.{name}"), false, OnError::Panic)?;
+        resets.push(quote!(self.#member = #value;));
+        let reset = options.method_ident("reset_", name.clone())?;
+        let is_default = options.method_ident("is_default_", name.clone())?;
+        // лайфтайм делает ограничение неглобальным, поэтому для типа без `PartialEq`
+        // метод просто недоступен, а не ломает весь вывод
+        let bound = quote!(where for<'__reset> #ty: ::std::cmp::PartialEq);
+        let reset_doc = format!(" Возвращает полю `{name}` значение по умолчанию.");
+        let is_default_doc = format!(" Равно ли поле `{name}` значению по умолчанию.");
+        methods.push(quote! {
+            #[doc = #reset_doc]
+            pub fn #reset(&mut self) {
+                self.#member = #value;
+            }
+
+            #[doc = #is_default_doc]
+            pub fn #is_default(&self) -> bool #bound {
+                self.#member == #value
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Возвращает всем полям значения по умолчанию.
+            pub fn reset(&mut self) {
+                #(#resets)*
+            }
+
+            #(#methods)*
+        }
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
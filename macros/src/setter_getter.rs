@@ -1,98 +1,873 @@
-use paste::paste;
+use macro_functions::{deref_target, type_from_args, FieldOptions};
 use proc_macro::TokenStream;
-use quote::{format_ident, quote};
+use quote::quote;
 use syn::{
-    parse_macro_input, Field, Fields, FieldsNamed, FieldsUnnamed, Ident, ItemStruct, PathSegment,
-    Type,
+    ext::IdentExt, meta::ParseNestedMeta, parse_macro_input, parse_quote,
+    AngleBracketedGenericArguments, Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput,
+    Expr, Field, GenericArgument, Ident, LitStr, Member, PathArguments, PathSegment, Type,
+    TypePath, Visibility,
 };
-use macro_functions::fast_impl;
-
-pub fn expand(
-    input: TokenStream,
-    attr_prefix: &str,
-    impl_: impl Fn(&Type, &Option<Ident>, Ident) -> proc_macro2::TokenStream,
-) -> TokenStream {
-    let struct_ = &parse_macro_input!(input);
-    let ItemStruct { fields, ident, generics, .. } = struct_;
-    let Fields::Named(FieldsNamed { named: fields, .. }) = fields else {
-        panic!("Only works on structs with named fields");
-    };
 
-    let mut is_acc_default = true;
-    let mut acc_every = vec![];
+/// Поле структуры или перечисления, для которого определяются методы.
+struct Accessor<'a> {
+    member: Member,
+    ty: &'a Type,
+    /// Атрибуты поля, для перечислений - из всех вариантов.
+    attrs: Vec<Attribute>,
+    docs: Vec<Attribute>,
+    /// Варианты перечисления, в которых есть поле, `None` для структуры.
+    variants: Option<Vec<&'a Ident>>,
+    /// Поле есть во всех вариантах перечисления.
+    is_total: bool,
+    /// Настройки `#[std_reset(...)]` поля.
+    options: FieldOptions,
+}
 
-    let mut is_glob_exclude = false;
-    let mut is_glob_include = false;
+impl<'a> Accessor<'a> {
+    fn new(index: usize, field: &'a Field, variants: Option<Vec<&'a Ident>>) -> Self {
+        let Field {
+            ident, ty, attrs, ..
+        } = field;
+        Self {
+            member: match ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            },
+            ty,
+            attrs: attrs.clone(),
+            docs: docs(attrs).cloned().collect(),
+            variants,
+            is_total: true,
+            options: FieldOptions::default(),
+        }
+    }
 
-    fields.iter().for_each(
-        |Field {
-             ident, ty, attrs, ..
-         }| {
-            let tmp = || {
-                let r = format!("{}_{{}}", attr_prefix);
-                let setter_ident = format_ident!("{}_{}", attr_prefix, ident.as_ref().unwrap());
-                impl_(ty, ident, setter_ident)
-            };
-            let [mut is_include, mut is_exclude] = [false; 2];
-            attrs.into_iter().for_each(|syn::Attribute { meta, .. }| {
-                if (meta.path().is_ident(&format!("include_{attr_prefix}ter"))) {
-                    is_include = true
+    fn collect(input: &'a DeriveInput) -> syn::Result<Vec<Self>> {
+        let mut accessors = Self::collect_fields(input)?;
+        for accessor in &mut accessors {
+            accessor.options = FieldOptions::parse(&accessor.attrs)?;
+        }
+        Ok(accessors)
+    }
+
+    fn collect_fields(input: &'a DeriveInput) -> syn::Result<Vec<Self>> {
+        match &input.data {
+            Data::Struct(DataStruct { fields, .. }) => Ok(fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| Self::new(i, field, None))
+                .collect()),
+            Data::Enum(DataEnum { variants, .. }) => {
+                let mut accessors: Vec<Self> = vec![];
+                for variant in variants {
+                    for (i, field) in variant.fields.iter().enumerate() {
+                        let accessor = Self::new(i, field, Some(vec![&variant.ident]));
+                        match accessors.iter_mut().find(|a| a.member == accessor.member) {
+                            Some(shared) if shared.ty != accessor.ty => {
+                                return Err(syn::Error::new_spanned(
+                                    &field.ty,
+                                    format!(
+                                        "field `{}` must have the same type in every variant",
+                                        shared.name()
+                                    ),
+                                ))
+                            }
+                            Some(shared) => {
+                                shared.attrs.extend(accessor.attrs);
+                                if shared.docs.is_empty() {
+                                    shared.docs = accessor.docs;
+                                }
+                                shared.variants.as_mut().unwrap().push(&variant.ident);
+                            }
+                            None => accessors.push(accessor),
+                        }
+                    }
                 }
-                if (meta.path().is_ident(&format!("exclude_{attr_prefix}ter"))) {
-                    is_exclude = true
+                accessors.iter_mut().for_each(|accessor| {
+                    accessor.is_total = accessor.variants.as_ref().unwrap().len() == variants.len()
+                });
+                Ok(accessors)
+            }
+            Data::Union(DataUnion { union_token, .. }) => Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            )),
+        }
+    }
+
+    /// Имя поля или его индекс с учётом `#[std_reset(rename = "...")]`.
+    fn name(&self) -> String {
+        self.options.name(match &self.member {
+            Member::Named(ident) => ident.unraw().to_string(),
+            Member::Unnamed(index) => index.index.to_string(),
+        })
+    }
+
+    /// Тип, возвращаемый методом: для полей, которых нет в части вариантов, - `Option`.
+    fn output(&self, ty: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        if self.is_total {
+            ty
+        } else {
+            quote!(::std::option::Option<#ty>)
+        }
+    }
+
+    /// Тело метода, который получает ссылку `value` на поле и возвращает `conv`.
+    fn read(&self, conv: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let member = &self.member;
+        match &self.variants {
+            None => quote! {
+                let Self { #member: value, .. } = self;
+                #conv
+            },
+            Some(variants) if self.is_total => quote! {
+                match self {
+                    #(Self::#variants { #member: value, .. })|* => #conv,
                 }
-            });
-            match [is_include, is_exclude, is_glob_include, is_glob_exclude] {
-                [true, true, _, _]
-                | [_, _, true, true]
-                | [true, _, _, true]
-                | [_, true, true, _] => {
-                    panic!("Поле может быть только exclude или include")
+            },
+            Some(variants) => quote! {
+                match self {
+                    #(Self::#variants { #member: value, .. })|* => ::std::option::Option::Some(#conv),
+                    _ => ::std::option::Option::None,
                 }
-                [true, false, _, _] => {
-                    is_glob_include = true;
+            },
+        }
+    }
 
-                    if is_acc_default {
-                        acc_every = vec![];
-                        is_acc_default = false;
+    /// Присваивание полю значения `value`.
+    ///
+    /// Для поля, которого нет в части вариантов, в `is_set` записывается, было ли поле изменено.
+    fn assign(&self, is_owned: bool) -> proc_macro2::TokenStream {
+        let member = &self.member;
+        match &self.variants {
+            None => quote!(self.#member = value;),
+            Some(variants) if !self.is_total => quote! {
+                let is_set = match self {
+                    #(Self::#variants { #member: field, .. })|* => {
+                        *field = value;
+                        true
+                    }
+                    _ => false,
+                };
+            },
+            Some(variants) => {
+                let receiver = if is_owned {
+                    quote!(&mut self)
+                } else {
+                    quote!(self)
+                };
+                quote! {
+                    match #receiver {
+                        #(Self::#variants { #member: field, .. })|* => *field = value,
                     }
+                }
+            }
+        }
+    }
 
-                    acc_every.push(tmp())
+    /// Тело метода, который получает изменяемую ссылку `field` на поле и возвращает `conv`.
+    fn modify(&self, is_owned: bool, conv: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let member = &self.member;
+        match &self.variants {
+            None => quote! {
+                let field = &mut self.#member;
+                #conv
+            },
+            Some(variants) => {
+                let receiver = if is_owned {
+                    quote!(&mut self)
+                } else {
+                    quote!(self)
+                };
+                quote! {
+                    match #receiver {
+                        #(Self::#variants { #member: field, .. })|* => #conv,
+                    }
                 }
-                [false, true, _, _] => {
-                    is_glob_exclude = true;
+            }
+        }
+    }
+}
+
+/// Поля, отобранные атрибутами `include_<name>` и `exclude_<name>`.
+///
+/// Если хотя бы одно поле отмечено `include_<name>`, отбираются только такие поля,
+/// иначе - все поля, кроме отмеченных `exclude_<name>`.
+pub fn select<'a, T>(items: impl IntoIterator<Item = (&'a [Attribute], T)>, name: &str) -> Vec<T> {
+    let mut is_acc_default = true;
+    let mut acc_every = vec![];
+
+    let mut is_glob_exclude = false;
+    let mut is_glob_include = false;
+
+    items.into_iter().for_each(|(attrs, item)| {
+        let [mut is_include, mut is_exclude] = [false; 2];
+        attrs.iter().for_each(|syn::Attribute { meta, .. }| {
+            if (meta.path().is_ident(&format!("include_{name}"))) {
+                is_include = true
+            }
+            if (meta.path().is_ident(&format!("exclude_{name}"))) {
+                is_exclude = true
+            }
+        });
+        match [is_include, is_exclude, is_glob_include, is_glob_exclude] {
+            [true, true, _, _] | [_, _, true, true] | [true, _, _, true] | [_, true, true, _] => {
+                panic!("Поле может быть только exclude или include")
+            }
+            [true, false, _, _] => {
+                is_glob_include = true;
+
+                if is_acc_default {
+                    acc_every = vec![];
+                    is_acc_default = false;
                 }
-                [false, false, _, _] => {
-                    if is_acc_default {
-                        acc_every.push(tmp())
-                    }
+
+                acc_every.push(item)
+            }
+            [false, true, _, _] => {
+                is_glob_exclude = true;
+            }
+            [false, false, _, _] => {
+                if is_acc_default {
+                    acc_every.push(item)
                 }
             }
-        },
-    );
+        }
+    });
+    acc_every
+}
+
+/// Генератор методов для поля и префикс его атрибутов: `get` или `set`.
+type Generator = (
+    &'static str,
+    fn(&DeriveInput, &Accessor) -> syn::Result<proc_macro2::TokenStream>,
+);
 
-    let methods = acc_every.into_iter();
+/// Методы всех генераторов в одном блоке `impl`.
+///
+/// Поля разбираются один раз, а ошибки генераторов объединяются.
+fn expand(input: TokenStream, generators: &[Generator]) -> TokenStream {
+    let input = &parse_macro_input!(input as DeriveInput);
+    let accessors = match Accessor::collect(input) {
+        Ok(accessors) => accessors,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut methods = vec![];
+    let mut error: Option<syn::Error> = None;
+    for (attr_prefix, generator) in generators {
+        let selected = select(
+            accessors
+                .iter()
+                .filter(|accessor| !accessor.options.skip)
+                .map(|accessor| (&accessor.attrs[..], accessor)),
+            &format!("{attr_prefix}ter"),
+        );
+        match selected
+            .into_iter()
+            .map(|accessor| generator(input, accessor))
+            .collect::<syn::Result<Vec<_>>>()
+        {
+            Ok(generated) => methods.extend(generated),
+            Err(err) => match &mut error {
+                Some(error) => error.combine(err),
+                None => error = Some(err),
+            },
+        }
+    }
+    if let Some(error) = error {
+        return error.to_compile_error().into();
+    }
+    let DeriveInput {
+        ident, generics, ..
+    } = input;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    fast_impl(struct_, quote!(#(#methods)*)).into()
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#methods)*
+        }
+    }
+    .into()
 }
 
 pub fn expand_setter(input: TokenStream) -> TokenStream {
-    expand(input, "set", |ty, ident, func_ident| {
+    expand(input, &[("set", setter)])
+}
+
+pub fn expand_getter(input: TokenStream) -> TokenStream {
+    expand(input, &[("get", getter)])
+}
+
+pub fn expand_accessors(input: TokenStream) -> TokenStream {
+    expand(input, &[("get", getter), ("set", setter)])
+}
+
+/// Имя, префикс и видимость метода, задаваемые через `name = "..."`, `prefix = "..."` и `vis = "..."`.
+#[derive(Default)]
+struct Naming {
+    name: Option<Ident>,
+    prefix: Option<String>,
+    vis: Option<Visibility>,
+}
+
+impl Naming {
+    /// Разбирает настройку именования, если `meta` к ней относится.
+    fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<bool> {
+        if meta.path.is_ident("name") {
+            self.name = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+        } else if meta.path.is_ident("prefix") {
+            self.prefix = Some(meta.value()?.parse::<LitStr>()?.value());
+        } else if meta.path.is_ident("vis") {
+            self.vis = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Имя метода может быть задано только для поля.
+    fn check_outer(&self) -> syn::Result<()> {
+        match &self.name {
+            Some(name) => Err(syn::Error::new_spanned(
+                name,
+                "`name` can only be specified for a field",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Видимость из `#[std_reset(vis = "...")]`, если она не задана атрибутом макроса.
+    fn or_options(&mut self, options: &FieldOptions) {
+        if self.vis.is_none() {
+            self.vis.clone_from(&options.vis);
+        }
+    }
+
+    fn or(self, outer: Self) -> Self {
+        Self {
+            name: self.name,
+            prefix: self.prefix.or(outer.prefix),
+            vis: self.vis.or(outer.vis),
+        }
+    }
+
+    fn ident(&self, default_prefix: &str, accessor: &Accessor) -> syn::Result<Ident> {
+        match &self.name {
+            Some(name) => Ok(name.clone()),
+            None => {
+                let prefix = self.prefix.as_deref().unwrap_or(default_prefix);
+                method_ident(&format!("{}{}", prefix, accessor.name()), accessor)
+            }
+        }
+    }
+
+    fn vis(&self) -> Visibility {
+        self.vis.clone().unwrap_or_else(|| parse_quote!(pub))
+    }
+}
+
+/// Идентификатор метода, например, `get_0` для неименованного поля.
+fn method_ident(name: &str, accessor: &Accessor) -> syn::Result<Ident> {
+    syn::parse_str(name).map_err(|_| {
+        syn::Error::new_spanned(
+            accessor.ty,
+            format!("`{name}` is not a valid method name, specify it with `name = \"...\"`"),
+        )
+    })
+}
+
+/// Doc-комментарии поля, которые переносятся на его методы.
+fn docs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("doc"))
+}
+
+/// Вид `set_` метода, задаваемый через `#[setter(mode = "...")]`.
+#[derive(Clone, Copy, Default)]
+enum SetterMode {
+    /// `fn set_x(mut self, value: T) -> Self`
+    #[default]
+    Owned,
+    /// `fn set_x(&mut self, value: T) -> &mut Self`
+    MutRef,
+    /// `fn set_x(&mut self, value: T)`
+    Plain,
+}
+
+/// Настройки `#[setter(...)]` структуры или поля.
+#[derive(Default)]
+struct SetterConfig {
+    mode: Option<SetterMode>,
+    naming: Naming,
+    /// `validate = path` - функция `fn(&T) -> Result<(), E>`, проверяющая новое значение.
+    validate: Option<Expr>,
+    /// `error = Type` - тип ошибки, возвращаемой `set_` методом с `validate`.
+    error: Option<Type>,
+    /// `on_change = Self::method` - функция `fn(&Self, old: &T, new: &T)`, вызываемая после изменения поля.
+    on_change: Option<Expr>,
+    /// `into` - метод принимает `impl Into<T>`.
+    is_into: bool,
+    /// `collection` - дополнительные методы для `Vec`, `HashMap`, `BTreeMap` и `Option`.
+    is_collection: bool,
+}
+
+impl SetterConfig {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut config = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("setter")) {
+            attr.parse_nested_meta(|meta| {
+                if config.naming.parse_meta(&meta)? {
+                    Ok(())
+                } else if meta.path.is_ident("mode") {
+                    let mode: LitStr = meta.value()?.parse()?;
+                    config.mode = Some(match mode.value().as_str() {
+                        "owned" => SetterMode::Owned,
+                        "mut_ref" => SetterMode::MutRef,
+                        "plain" => SetterMode::Plain,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                mode,
+                                "expected `owned`, `mut_ref` or `plain`",
+                            ))
+                        }
+                    });
+                    Ok(())
+                } else if meta.path.is_ident("validate") {
+                    config.validate = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("error") {
+                    config.error = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("on_change") {
+                    config.on_change = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("into") {
+                    config.is_into = true;
+                    Ok(())
+                } else if meta.path.is_ident("collection") {
+                    config.is_collection = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported setter attribute"))
+                }
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// Проверки, хуки и методы коллекций задаются только для поля.
+    fn check_outer(&self) -> syn::Result<()> {
+        self.naming.check_outer()?;
+        if self.is_collection {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "`collection` can only be specified for a field",
+            ));
+        }
+        match (&self.validate, &self.on_change) {
+            (Some(expr), _) | (_, Some(expr)) => Err(syn::Error::new_spanned(
+                expr,
+                "`validate` and `on_change` can only be specified for a field",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Настройки поля дополняются настройками структуры.
+    fn or(self, outer: Self) -> Self {
+        Self {
+            mode: self.mode.or(outer.mode),
+            naming: self.naming.or(outer.naming),
+            validate: self.validate,
+            error: self.error.or(outer.error),
+            on_change: self.on_change,
+            is_into: self.is_into || outer.is_into,
+            is_collection: self.is_collection,
+        }
+    }
+}
+
+/// Коллекция, для поля которой `#[setter(collection)]` определяет дополнительные методы.
+enum Collection<'a> {
+    /// `Vec<T>`
+    Vec(&'a Type),
+    /// `HashMap<K, V>`
+    HashMap(&'a Type, &'a Type),
+    /// `BTreeMap<K, V>`
+    BTreeMap(&'a Type, &'a Type),
+    /// `Option<T>`
+    Option(&'a Type),
+}
+
+impl<'a> Collection<'a> {
+    fn of(ty: &'a Type) -> syn::Result<Self> {
+        let error = || {
+            syn::Error::new_spanned(
+                ty,
+                "`#[setter(collection)]` requires `Vec`, `HashMap`, `BTreeMap` or `Option`",
+            )
+        };
+        let Type::Path(TypePath { path, qself: None }) = ty else {
+            return Err(error());
+        };
+        let PathSegment { ident, arguments } = path.segments.last().ok_or_else(error)?;
+        let PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) = arguments
+        else {
+            return Err(error());
+        };
+        let args: Vec<_> = args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect();
+        Ok(match (ident.to_string().as_str(), &args[..]) {
+            ("Vec", [item, ..]) => Self::Vec(item),
+            ("HashMap", [key, value, ..]) => Self::HashMap(key, value),
+            ("BTreeMap", [key, value, ..]) => Self::BTreeMap(key, value),
+            ("Option", [inner]) => Self::Option(inner),
+            _ => return Err(error()),
+        })
+    }
+}
+
+/// Методы `#[setter(collection)]`: `push_`, `extend_`, `insert_`, `remove_`, `take_` и `clear_`.
+///
+/// Методы, которые только изменяют коллекцию, имеют тот же вид, что и `set_` метод,
+/// а `take_` и `remove_` всегда принимают `&mut self` и возвращают извлечённое значение.
+fn collection_methods(
+    accessor: &Accessor,
+    mode: SetterMode,
+    vis: &Visibility,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = accessor.name();
+    let is_owned = matches!(mode, SetterMode::Owned);
+    let (receiver, output, ret) = match mode {
+        SetterMode::Owned => (quote!(mut self), quote!(-> Self), quote!(self)),
+        SetterMode::MutRef => (quote!(&mut self), quote!(-> &mut Self), quote!(self)),
+        SetterMode::Plain => (quote!(&mut self), quote!(), quote!()),
+    };
+    let must_use = is_owned.then(|| quote!(#[must_use]));
+    let chained = |prefix: &str, doc: String, params, conv| {
+        let ident = method_ident(&format!("{prefix}_{name}"), accessor)?;
+        let body = accessor.modify(is_owned, conv);
+        Ok::<_, syn::Error>(quote! {
+            #[doc = #doc]
+            #must_use
+            #vis fn #ident(#receiver, #params) #output {
+                #body;
+                #ret
+            }
+        })
+    };
+    let clear = |conv| chained("clear", format!(" Очищает поле `{name}`."), quote!(), conv);
+
+    let map = |key, value, bound| {
+        let remove = method_ident(&format!("remove_{name}"), accessor)?;
+        let remove_doc = format!(" Удаляет из поля `{name}` значение по ключу `key`.");
+        let body = accessor.modify(false, quote!(field.remove(key)));
+        Ok::<_, syn::Error>(vec![
+            chained(
+                "insert",
+                format!(" Вставляет в поле `{name}` значение `value` по ключу `key`."),
+                quote!(key: #key, value: #value),
+                quote!(field.insert(key, value)),
+            )?,
+            quote! {
+                #[doc = #remove_doc]
+                #vis fn #remove<Q>(&mut self, key: &Q) -> ::std::option::Option<#value>
+                where
+                    #key: ::std::borrow::Borrow<Q>,
+                    Q: #bound + ?::std::marker::Sized,
+                {
+                    #body
+                }
+            },
+        ])
+    };
+
+    let methods = match Collection::of(accessor.ty)? {
+        Collection::Vec(item) => vec![
+            chained(
+                "push",
+                format!(" Добавляет элемент в конец поля `{name}`."),
+                quote!(value: #item),
+                quote!(field.push(value)),
+            )?,
+            chained(
+                "extend",
+                format!(" Добавляет элементы в конец поля `{name}`."),
+                quote!(values: impl ::std::iter::IntoIterator<Item = #item>),
+                quote!(::std::iter::Extend::extend(field, values)),
+            )?,
+            clear(quote!(field.clear()))?,
+        ],
+        Collection::HashMap(key, value) => {
+            map(key, value, quote!(::std::hash::Hash + ::std::cmp::Eq))?
+        }
+        Collection::BTreeMap(key, value) => map(key, value, quote!(::std::cmp::Ord))?,
+        Collection::Option(inner) => {
+            let take = method_ident(&format!("take_{name}"), accessor)?;
+            let take_doc = format!(" Забирает значение поля `{name}`, оставляя `None`.");
+            let body = accessor.modify(false, quote!(field.take()));
+            vec![
+                quote! {
+                    #[doc = #take_doc]
+                    #vis fn #take(&mut self) -> ::std::option::Option<#inner> {
+                        #body
+                    }
+                },
+                clear(quote!(*field = ::std::option::Option::None))?,
+            ]
+        }
+    };
+    Ok(quote!(#(#methods)*))
+}
+
+/// Метод `set_` и методы коллекций для поля.
+fn setter(input: &DeriveInput, accessor: &Accessor) -> syn::Result<proc_macro2::TokenStream> {
+    let Accessor {
+        ty, attrs, docs, ..
+    } = accessor;
+    let outer = SetterConfig::parse(&input.attrs)?;
+    outer.check_outer()?;
+    let mut config = SetterConfig::parse(attrs)?;
+    config.naming.or_options(&accessor.options);
+    config.is_into |= accessor.options.is_into;
+    let config = config.or(outer);
+
+    let func_ident = config.naming.ident("set_", accessor)?;
+    let vis = config.naming.vis();
+
+    // поле есть не во всех вариантах: метод изменяет значение на месте и сообщает, было ли поле
+    let mode = match accessor.is_total {
+        true => config.mode.unwrap_or_default(),
+        false => SetterMode::Plain,
+    };
+    let (receiver, mut output, mut ret) = match mode {
+        _ if !accessor.is_total => (quote!(&mut self), Some(quote!(bool)), Some(quote!(is_set))),
+        SetterMode::Owned => (quote!(mut self), Some(quote!(Self)), Some(quote!(self))),
+        SetterMode::MutRef => (
+            quote!(&mut self),
+            Some(quote!(&mut Self)),
+            Some(quote!(self)),
+        ),
+        SetterMode::Plain => (quote!(&mut self), None, None),
+    };
+    let must_use = matches!(mode, SetterMode::Owned).then(|| quote!(#[must_use]));
+
+    let (param, into) = if config.is_into {
+        (
+            quote!(impl ::std::convert::Into<#ty>),
+            Some(quote!(let value = ::std::convert::Into::into(value);)),
+        )
+    } else {
+        (quote!(#ty), None)
+    };
+
+    let validate = config.validate.as_ref().map(|validate| {
+        let error = config.error.clone().unwrap_or_else(|| {
+            parse_quote!(
+                ::std::boxed::Box<
+                    dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync,
+                >
+            )
+        });
+        let ok = output.take().unwrap_or_else(|| quote!(()));
+        let value = ret.take().unwrap_or_else(|| quote!(()));
+        ret = Some(quote!(::std::result::Result::Ok(#value)));
+        // метод забирает `self`, поэтому при ошибке структура возвращается вместе с ней
+        if let SetterMode::Owned = mode {
+            output = Some(quote!(::std::result::Result<#ok, (Self, #error)>));
+            return quote! {
+                if let ::std::result::Result::Err(err) = (#validate)(&value) {
+                    return ::std::result::Result::Err((self, ::std::convert::From::from(err)));
+                }
+            };
+        }
+        output = Some(quote!(::std::result::Result<#ok, #error>));
         quote! {
-            pub fn #func_ident(&mut self, value: #ty) -> Self {
-                self.#ident = value;
-                (*self).clone()
+            (#validate)(&value)?;
+        }
+    });
+
+    let assign = match (&config.on_change, &accessor.variants) {
+        (None, _) => accessor.assign(matches!(mode, SetterMode::Owned)),
+        (Some(on_change), None) => {
+            let member = &accessor.member;
+            quote! {
+                let old = ::std::mem::replace(&mut self.#member, value);
+                (#on_change)(&self, &old, &self.#member);
             }
         }
+        (Some(on_change), Some(_)) => {
+            return Err(syn::Error::new_spanned(
+                on_change,
+                "`on_change` is not supported for enums",
+            ))
+        }
+    };
+    let output = output.map(|output| quote!(-> #output));
+    let collection = match config.is_collection {
+        true if !accessor.is_total => {
+            return Err(syn::Error::new_spanned(
+                accessor.ty,
+                format!(
+                    "`collection` requires field `{}` in every variant",
+                    accessor.name()
+                ),
+            ))
+        }
+        true => Some(collection_methods(accessor, mode, &vis)?),
+        false => None,
+    };
+
+    Ok(quote! {
+        #(#docs)*
+        #must_use
+        #vis fn #func_ident(#receiver, value: #param) #output {
+            #into
+            #validate
+            #assign
+            #ret
+        }
+        #collection
     })
 }
 
-pub fn expand_getter(input: TokenStream) -> TokenStream {
-    expand(input, "get", |ty, ident, func_ident| {
+/// Вид `get_` метода, задаваемый через `#[getter(...)]`.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum GetterMode {
+    /// `fn get_x(&self) -> &T`
+    #[default]
+    Ref,
+    /// `fn get_x(&self) -> T`, где `T: Copy`
+    Copy,
+    /// `fn get_x(&self) -> T`, где `T: Clone`
+    Clone,
+    /// `fn get_x(&self) -> &str` для `String`, `&[T]` для `Vec<T>`, `Option<&T>` для `Option<T>` и т.д.
+    Deref,
+}
+
+/// Настройки `#[getter(...)]` структуры или поля.
+#[derive(Default)]
+struct GetterConfig {
+    mode: Option<GetterMode>,
+    is_mut: bool,
+    naming: Naming,
+}
+
+impl GetterConfig {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut config = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("getter")) {
+            attr.parse_nested_meta(|meta| {
+                if config.naming.parse_meta(&meta)? {
+                    return Ok(());
+                }
+                let mode = if meta.path.is_ident("copy") {
+                    GetterMode::Copy
+                } else if meta.path.is_ident("clone") {
+                    GetterMode::Clone
+                } else if meta.path.is_ident("deref") {
+                    GetterMode::Deref
+                } else if meta.path.is_ident("mut") {
+                    config.is_mut = true;
+                    return Ok(());
+                } else {
+                    return Err(meta.error("unsupported getter attribute"));
+                };
+                if config.mode.replace(mode).is_some() {
+                    return Err(
+                        meta.error("only one of `copy`, `clone` or `deref` can be specified")
+                    );
+                }
+                Ok(())
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// Настройки поля дополняются настройками структуры.
+    fn or(self, outer: Self) -> Self {
+        Self {
+            mode: self.mode.or(outer.mode),
+            is_mut: self.is_mut || outer.is_mut,
+            naming: self.naming.or(outer.naming),
+        }
+    }
+}
+
+/// Внутренний тип `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(TypePath { path, qself: None }) = ty else {
+        return None;
+    };
+    let PathSegment { ident, arguments } = path.segments.last()?;
+    (ident == "Option").then(|| type_from_args(arguments).ok())?
+}
+
+/// Методы `get_` для поля.
+fn getter(input: &DeriveInput, accessor: &Accessor) -> syn::Result<proc_macro2::TokenStream> {
+    let Accessor {
+        ty, attrs, docs, ..
+    } = accessor;
+    let outer = GetterConfig::parse(&input.attrs)?;
+    outer.naming.check_outer()?;
+    let mut config = GetterConfig::parse(attrs)?;
+    config.naming.or_options(&accessor.options);
+    let config = config.or(outer);
+
+    let func_ident = config.naming.ident("get_", accessor)?;
+    let vis = config.naming.vis();
+
+    let (output, conv) = match config.mode.unwrap_or_default() {
+        GetterMode::Ref => (quote!(&#ty), quote!(value)),
+        GetterMode::Copy => (quote!(#ty), quote!(*value)),
+        GetterMode::Clone => (quote!(#ty), quote!(::std::clone::Clone::clone(value))),
+        GetterMode::Deref => {
+            if let Some(inner) = option_inner(ty) {
+                (
+                    quote!(::std::option::Option<&#inner>),
+                    quote!(::std::option::Option::as_ref(value)),
+                )
+            } else if let Some(target) = deref_target(ty) {
+                (quote!(&#target), quote!(&**value))
+            } else {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "`#[getter(deref)]` is not supported for this type",
+                ));
+            }
+        }
+    };
+    let output = accessor.output(output);
+    let body = accessor.read(conv);
+
+    let mut_getter = if config.is_mut {
+        let name = match &config.naming.name {
+            Some(name) => name.unraw().to_string(),
+            None => accessor.name(),
+        };
+        let mut_ident = method_ident(&format!("{name}_mut"), accessor)?;
+        let output = accessor.output(quote!(&mut #ty));
+        let body = accessor.read(quote!(value));
         quote! {
-            pub fn #func_ident(&self) -> #ty {
-                self.#ident
+            #(#docs)*
+            #vis fn #mut_ident(&mut self) -> #output {
+                #body
             }
         }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        #(#docs)*
+        #vis fn #func_ident(&self) -> #output {
+            #body
+        }
+        #mut_getter
     })
 }
@@ -0,0 +1,282 @@
+use macro_functions::FieldOptions;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, Data, DataEnum, DataStruct, DataUnion,
+    DeriveInput, Fields, GenericParam, Lifetime, Member,
+};
+
+use crate::patch::derives;
+
+/// Методы `<Имя>Vec`, с которыми не должны совпадать имена столбцов.
+const RESERVED: [&str; 8] = [
+    "new",
+    "with_capacity",
+    "len",
+    "is_empty",
+    "push",
+    "get",
+    "iter",
+    "swap_remove",
+];
+
+fn try_expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let DeriveInput {
+        attrs,
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let fields = match data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                enum_token,
+                "enums are not supported",
+            ))
+        }
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(syn::Error::new_spanned(
+                union_token,
+                "unions are not supported",
+            ))
+        }
+    };
+    if fields.is_empty() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "structs without fields are not supported",
+        ));
+    }
+    FieldOptions::validate(data, "Soa", &["skip"])?;
+
+    let soa = format_ident!("{ident}Vec");
+    let soa_ref = format_ident!("{ident}Ref");
+    let derives = derives(attrs, "soa")?;
+
+    let members: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::from(i),
+        })
+        .collect();
+    let first = &members[0];
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__{}", i))
+        .collect();
+    let iters: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("__iter{}", i))
+        .collect();
+    let tys: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let field_vis: Vec<_> = fields.iter().map(|field| &field.vis).collect();
+
+    // столбцы закрыты, чтобы их длины не расходились, и доступны как срезы
+    let mut columns = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let options = FieldOptions::parse(&field.attrs)?;
+        let name = match &field.ident {
+            Some(ident) => ident.unraw().to_string(),
+            None => format!("column_{i}"),
+        };
+        let getter = options.method_ident("", name)?;
+        let getter_mut = format_ident!("{getter}_mut");
+        if RESERVED.contains(&getter.to_string().as_str()) {
+            let message = format!(
+                "column `{getter}` conflicts with a method of `{soa}`, \
+                 use `#[std_reset(rename = \"...\")]`"
+            );
+            return Err(match &options.rename {
+                Some(rename) => syn::Error::new_spanned(rename, message),
+                None => syn::Error::new_spanned(field, message),
+            });
+        }
+        let vis = options.vis.as_ref().unwrap_or(&field.vis);
+        let member = &members[i];
+        let ty = &field.ty;
+        let doc = format!(" Столбец поля `{}`.", quote!(#member));
+        let doc_mut = format!(" Изменяемый столбец поля `{}`.", quote!(#member));
+        columns.push(quote! {
+            #[doc = #doc]
+            #vis fn #getter(&self) -> &[#ty] {
+                &self.#member
+            }
+
+            #[doc = #doc_mut]
+            #vis fn #getter_mut(&mut self) -> &mut [#ty] {
+                &mut self.#member
+            }
+        });
+    }
+
+    let lifetime: Lifetime = parse_quote!('__soa);
+    let mut ref_generics = generics.clone();
+    ref_generics
+        .params
+        .insert(0, GenericParam::Lifetime(parse_quote!(#lifetime)));
+    let args = generics.params.iter().map(|param| match param {
+        GenericParam::Lifetime(param) => {
+            let lifetime = &param.lifetime;
+            quote!(#lifetime)
+        }
+        GenericParam::Type(param) => {
+            let ident = &param.ident;
+            quote!(#ident)
+        }
+        GenericParam::Const(param) => {
+            let ident = &param.ident;
+            quote!(#ident)
+        }
+    });
+    let ref_ty = quote!(#soa_ref<'_, #(#args),*>);
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (ref_impl_generics, ref_ty_generics, _) = ref_generics.split_for_impl();
+
+    let (soa_definition, ref_definition) = match fields {
+        Fields::Named(_) => (
+            quote! {
+                #where_clause {
+                    #(#members: ::std::vec::Vec<#tys>,)*
+                }
+            },
+            quote! {
+                #where_clause {
+                    #(#field_vis #members: &#lifetime #tys,)*
+                }
+            },
+        ),
+        _ => (
+            quote! {
+                (#(::std::vec::Vec<#tys>,)*) #where_clause;
+            },
+            quote! {
+                (#(#field_vis &#lifetime #tys,)*) #where_clause;
+            },
+        ),
+    };
+
+    let soa_doc = format!(" Столбцы полей [`{ident}`]: по одному [`Vec`] на поле.");
+    let ref_doc = format!(" Ссылки на поля одного элемента [`{soa}`].");
+
+    Ok(quote! {
+        #[doc = #soa_doc]
+        #[derive(#(#derives),*)]
+        #vis struct #soa #generics #soa_definition
+
+        #[doc = #ref_doc]
+        #vis struct #soa_ref #ref_generics #ref_definition
+
+        impl #ref_impl_generics ::std::clone::Clone for #soa_ref #ref_ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #ref_impl_generics ::std::marker::Copy for #soa_ref #ref_ty_generics #where_clause {}
+
+        impl #impl_generics ::std::default::Default for #soa #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#members: ::std::vec::Vec::new(),)*
+                }
+            }
+        }
+
+        impl #impl_generics #soa #ty_generics #where_clause {
+            /// Пустые столбцы.
+            pub fn new() -> Self {
+                ::std::default::Default::default()
+            }
+
+            /// Пустые столбцы с местом для `capacity` элементов.
+            pub fn with_capacity(capacity: usize) -> Self {
+                Self {
+                    #(#members: ::std::vec::Vec::with_capacity(capacity),)*
+                }
+            }
+
+            #(#columns)*
+
+            /// Количество элементов.
+            pub fn len(&self) -> usize {
+                self.#first.len()
+            }
+
+            /// Нет ни одного элемента.
+            pub fn is_empty(&self) -> bool {
+                self.#first.is_empty()
+            }
+
+            /// Добавляет элемент в конец.
+            pub fn push(&mut self, value: #ident #ty_generics) {
+                let #ident { #(#members: #bindings),* } = value;
+                #(self.#members.push(#bindings);)*
+            }
+
+            /// Ссылки на поля элемента с номером `index`.
+            pub fn get(&self, index: usize) -> ::std::option::Option<#ref_ty> {
+                if index >= self.len() {
+                    return ::std::option::Option::None;
+                }
+                ::std::option::Option::Some(#soa_ref {
+                    #(#members: &self.#members[index],)*
+                })
+            }
+
+            /// Итератор по ссылкам на поля элементов.
+            pub fn iter(&self) -> impl ::std::iter::Iterator<Item = #ref_ty> + '_ {
+                (0..self.len()).map(move |index| #soa_ref {
+                    #(#members: &self.#members[index],)*
+                })
+            }
+
+            /// Удаляет элемент с номером `index` и возвращает его, заменяя последним элементом.
+            ///
+            /// # Panics
+            /// Если `index` не меньше длины.
+            pub fn swap_remove(&mut self, index: usize) -> #ident #ty_generics {
+                #ident {
+                    #(#members: self.#members.swap_remove(index),)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std_reset::traits::of_to::Of<::std::vec::Vec<#ident #ty_generics>>
+            for #soa #ty_generics #where_clause
+        {
+            fn of(value: ::std::vec::Vec<#ident #ty_generics>) -> Self {
+                let mut soa = Self::with_capacity(value.len());
+                for item in value {
+                    soa.push(item);
+                }
+                soa
+            }
+        }
+
+        impl #impl_generics ::std_reset::traits::of_to::Of<#soa #ty_generics>
+            for ::std::vec::Vec<#ident #ty_generics> #where_clause
+        {
+            fn of(value: #soa #ty_generics) -> Self {
+                let mut items = ::std::vec::Vec::with_capacity(value.len());
+                #(let mut #iters = ::std::iter::IntoIterator::into_iter(value.#members);)*
+                while let (#(::std::option::Option::Some(#bindings),)*) = (#(#iters.next(),)*) {
+                    items.push(#ident { #(#members: #bindings),* });
+                }
+                items
+            }
+        }
+    })
+}
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input);
+    match try_expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
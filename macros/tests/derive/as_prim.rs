@@ -0,0 +1,92 @@
+use std_reset::prelude::{AsPrim, To, TryTo};
+
+#[test]
+fn discriminants() {
+    #[derive(AsPrim, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Command {
+        Ping = 1,
+        Pong,
+        Close = 0xFF,
+    }
+
+    assert_eq!(Command::Ping.as_::<u8>(), 1);
+    assert_eq!(Command::Pong.as_::<i64>(), 2);
+    assert_eq!(Command::Close.as_::<f32>(), 255.0);
+    assert_eq!(Command::Close.to::<u8>(), 255);
+
+    assert_eq!(2_u8.try_to::<Command>(), Ok(Command::Pong));
+    assert_eq!(Command::try_from(255), Ok(Command::Close));
+    let err = 0_u8.try_to::<Command>().unwrap_err();
+    assert_eq!(err, CommandDiscriminantError(0));
+    assert_eq!(err.to_string(), "unknown discriminant `0` of `Command`");
+}
+
+#[test]
+fn signed_repr() {
+    #[derive(AsPrim, Debug, PartialEq, Clone, Copy)]
+    #[repr(i16)]
+    enum Level {
+        Low = -1,
+        High = 300,
+    }
+
+    assert_eq!(Level::Low.as_::<i16>(), -1);
+    assert_eq!(Level::High.as_::<u8>(), 300_i16 as u8);
+    assert_eq!(Level::try_from(-1), Ok(Level::Low));
+    assert!(Level::try_from(0).is_err());
+
+    let values: Vec<i16> = [Level::Low, Level::High].into_iter().map(To::to).collect();
+    assert_eq!(values, [-1, 300]);
+}
+
+#[test]
+fn trait_bounds() {
+    use std_reset::traits::as_prim::{FromPrim, ToPrim};
+
+    fn describe<T: ToPrim>(value: T) -> String {
+        value.to_string()
+    }
+    fn widen<T: AsPrim>(value: T) -> f64 {
+        f64::as_from(value)
+    }
+
+    #[derive(AsPrim, Clone, Copy)]
+    #[repr(u8)]
+    enum Digit {
+        Seven = 7,
+    }
+
+    assert_eq!(describe(Digit::Seven.as_::<u8>()), "7");
+    assert_eq!(widen(Digit::Seven.as_::<i32>()), 7.0);
+}
+
+#[test]
+fn generic_code() {
+    use std_reset::prelude::Of;
+
+    fn encode<T>(values: Vec<T>) -> Vec<u16>
+    where
+        u16: Of<T>,
+    {
+        values.into_iter().map(u16::of).collect()
+    }
+
+    fn decode<T: TryFrom<u16>>(values: &[u16]) -> Option<Vec<T>> {
+        values.iter().map(|&value| value.try_to().ok()).collect()
+    }
+
+    #[derive(AsPrim, Debug, PartialEq)]
+    #[repr(u16)]
+    enum Status {
+        Ok = 200,
+        NotFound = 404,
+    }
+
+    assert_eq!(encode(vec![Status::Ok, Status::NotFound]), [200, 404]);
+    assert_eq!(
+        decode::<Status>(&[404, 200]),
+        Some(vec![Status::NotFound, Status::Ok])
+    );
+    assert_eq!(decode::<Status>(&[500]), None);
+}
@@ -1,4 +1,4 @@
-#![allow(unused)]
+#![allow(unused, clippy::inconsistent_digit_grouping, clippy::default_constructed_unit_structs)]
 
 use std::default;
 
@@ -102,4 +102,191 @@ fn enum_() {
             first: UnnamedStruct
         }
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn with_factory() {
+    fn port() -> u16 {
+        8080
+    }
+
+    mod defaults {
+        pub fn hosts() -> Vec<String> {
+            vec!["localhost".to_string()]
+        }
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Config {
+        #[default(with = port)]
+        port: u16,
+        #[default(with = defaults::hosts)]
+        hosts: Vec<String>,
+    }
+    assert_eq!(
+        Config::default(),
+        Config {
+            port: 8080,
+            hosts: vec!["localhost".to_string()],
+        }
+    );
+}
+
+#[test]
+fn const_default() {
+    const fn timeout() -> u64 {
+        30
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    #[default(const)]
+    struct Config {
+        #[default("localhost")]
+        host: &'static str,
+        #[default(8080)]
+        port: u16,
+        #[default(with = timeout)]
+        timeout: u64,
+    }
+
+    static CONFIG: Config = Config::DEFAULT;
+
+    assert_eq!(CONFIG, Config::default());
+    assert_eq!(CONFIG.timeout, 30);
+
+    #[derive(Debug, Default, PartialEq)]
+    #[default(const)]
+    enum Level {
+        Low,
+        #[default]
+        Custom(#[default(5)] u8),
+    }
+    const LEVEL: Level = Level::DEFAULT;
+    assert_eq!(LEVEL, Level::Custom(5));
+}
+
+#[test]
+fn default_with() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Config {
+        #[default("localhost")]
+        host: &'static str,
+        #[default(8080)]
+        port: u16,
+    }
+    assert_eq!(
+        Config::default_with(|config| config.port = 3000),
+        Config {
+            host: "localhost",
+            port: 3000,
+        }
+    );
+}
+
+
+#[test]
+fn field_bounds() {
+    use std::marker::PhantomData;
+
+    struct NoDefault;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Explicit<T> {
+        #[default(Vec::new())]
+        items: Vec<T>,
+        #[default(0)]
+        len: usize,
+    }
+    assert_eq!(Explicit::<NoDefault>::default().len, 0);
+
+    #[derive(Debug, Default)]
+    struct Marker<T>(PhantomData<T>, #[default(1)] u8);
+    assert_eq!(Marker::<NoDefault>::default().1, 1);
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Wrapper<T: std::default::Default + Clone> {
+        inner: Option<T>,
+        value: T,
+    }
+    assert_eq!(
+        Wrapper::<i32>::default(),
+        Wrapper {
+            inner: None,
+            value: 0
+        }
+    );
+}
+
+#[test]
+fn custom_bound() {
+    trait Factory {
+        fn create() -> Self;
+    }
+
+    impl Factory for i32 {
+        fn create() -> Self {
+            7
+        }
+    }
+
+    #[derive(Debug, Default, PartialEq)]
+    #[default(bound = "T: Factory")]
+    struct Value<T>(#[default(T::create())] T);
+    assert_eq!(Value::<i32>::default(), Value(7));
+
+    #[derive(Debug, Default, PartialEq)]
+    #[default(bound = "")]
+    struct Empty<T>(Option<T>);
+    assert_eq!(Empty::<()>::default(), Empty(None));
+}
+
+
+#[test]
+fn env_and_parse() {
+    use std::{env, net::Ipv4Addr};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Config {
+        #[default(env = "STD_RESET_TEST_PORT", fallback = 8080)]
+        port: u16,
+        #[default(env = "STD_RESET_TEST_HOST")]
+        host: String,
+        #[default(parse = "127.0.0.1")]
+        ip: Ipv4Addr,
+    }
+
+    env::set_var("STD_RESET_TEST_HOST", "example.com");
+    assert_eq!(
+        Config::default(),
+        Config {
+            port: 8080,
+            host: "example.com".to_string(),
+            ip: Ipv4Addr::LOCALHOST,
+        }
+    );
+
+    env::set_var("STD_RESET_TEST_PORT", "3000");
+    assert_eq!(Config::try_default().unwrap().port, 3000);
+
+    env::set_var("STD_RESET_TEST_PORT", "port");
+    let err = Config::try_default().unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("failed to parse `Config.port` from environment variable `STD_RESET_TEST_PORT`"));
+
+    env::remove_var("STD_RESET_TEST_PORT");
+    env::remove_var("STD_RESET_TEST_HOST");
+    assert_eq!(
+        Config::try_default().unwrap_err().to_string(),
+        "environment variable `STD_RESET_TEST_HOST` for `Config.host` is not set"
+    );
+}
+
+#[test]
+#[should_panic(expected = "failed to parse `Timeout.0` from \"10s\"")]
+fn parse_panic() {
+    #[derive(Debug, Default)]
+    struct Timeout(#[default(parse = "10s")] u64);
+
+    Timeout::default();
+}
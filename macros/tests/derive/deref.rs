@@ -24,3 +24,74 @@ fn deref_every_type_structure() {
         "1"
     );
 }
+
+#[test]
+fn deref_target_readonly_and_forward() {
+    #[derive(Deref)]
+    #[deref(target = str)]
+    struct Name(String);
+    let name = Name("crab".to_string());
+    assert_eq!(name.len(), 4);
+    assert_eq!(&*name, "crab");
+
+    #[derive(Deref)]
+    struct Numbers {
+        #[deref(target, readonly)]
+        inner: Vec<i32>,
+    }
+    let numbers = Numbers {
+        inner: vec![1, 2, 3],
+    };
+    assert_eq!(&*numbers, &[1, 2, 3][..]);
+
+    #[derive(Deref)]
+    struct Inner(Vec<i32>);
+    #[derive(Deref)]
+    #[deref(forward)]
+    struct Outer(Inner);
+    let mut outer = Outer(Inner(vec![1]));
+    outer.push(2);
+    assert_eq!(*outer, vec![1, 2]);
+}
+
+#[test]
+fn deref_enum() {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Uuid(u128);
+
+    #[derive(Debug, Deref)]
+    enum Id {
+        User(Uuid),
+        Org(Uuid),
+    }
+
+    assert_eq!(*Id::User(Uuid(1)), Uuid(1));
+    let mut id = Id::Org(Uuid(2));
+    id.0 += 1;
+    assert_eq!(*id, Uuid(3));
+
+    #[derive(Deref)]
+    #[deref(target, readonly)]
+    enum Name {
+        Short(String),
+        Full {
+            _first: &'static str,
+            #[deref]
+            full: String,
+        },
+        Anonymous {
+            label: String,
+        },
+    }
+
+    assert_eq!(Name::Short("Ann".to_string()).len(), 3);
+    let full = Name::Full {
+        _first: "Ann",
+        full: "Ann Lee".to_string(),
+    };
+    assert_eq!(&*full, "Ann Lee");
+    assert!(Name::Anonymous {
+        label: "?".to_string()
+    }
+    .starts_with('?'));
+}
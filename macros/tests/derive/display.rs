@@ -7,3 +7,115 @@ fn display() {
 
     assert_eq!(format!("{:?}", Exmpl {}), format!("{}", Exmpl {}));
 }
+
+#[test]
+fn format_string() {
+    #[derive(Display)]
+    #[display("{name} is {age:>3} years old")]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    #[derive(Display)]
+    #[display("({}, {1:.1})")]
+    struct Point(i32, f32);
+
+    #[derive(Display)]
+    #[display("{} items", self.0.len())]
+    struct Items(Vec<i32>);
+
+    let person = Person {
+        name: "Bob".to_string(),
+        age: 42,
+    };
+    assert_eq!(person.to_string(), "Bob is  42 years old");
+    assert_eq!(Point(1, 2.25).to_string(), "(1, 2.2)");
+    assert_eq!(Items(vec![1, 2, 3]).to_string(), "3 items");
+}
+
+#[test]
+fn enum_() {
+    #[derive(Display, Debug)]
+    enum Shape {
+        #[display("circle r={radius}")]
+        Circle { radius: f64 },
+        #[display("rect {0}x{1}")]
+        Rect(u32, u32),
+        #[display(skip)]
+        Empty,
+        Unknown,
+    }
+
+    assert_eq!(Shape::Circle { radius: 1.5 }.to_string(), "circle r=1.5");
+    assert_eq!(Shape::Rect(2, 3).to_string(), "rect 2x3");
+    assert_eq!(Shape::Empty.to_string(), "");
+    assert_eq!(Shape::Unknown.to_string(), "Unknown");
+}
+
+#[test]
+fn skip_field() {
+    #![allow(dead_code)]
+
+    #[derive(Display)]
+    struct User {
+        login: String,
+        #[display(skip)]
+        password: String,
+    }
+
+    #[derive(Display)]
+    struct Token(u32, #[display(skip)] String);
+
+    let user = User {
+        login: "admin".to_string(),
+        password: "qwerty".to_string(),
+    };
+    assert_eq!(user.to_string(), r#"User { login: "admin", .. }"#);
+    assert_eq!(Token(1, "secret".to_string()).to_string(), "Token(1)");
+}
+
+#[test]
+fn generic_bounds() {
+    #[derive(Display)]
+    #[display("{0}")]
+    struct Wrapper<T>(T);
+
+    #[derive(Display)]
+    #[display("{value:?} = {hex:#x} ({label})")]
+    struct Entry<T, H, L> {
+        value: T,
+        hex: H,
+        label: L,
+    }
+
+    #[derive(Display, Debug)]
+    enum Either<L, R> {
+        #[display("left {0}")]
+        Left(L),
+        Right(R),
+    }
+
+    #[derive(Display)]
+    struct Partial<T> {
+        value: T,
+        #[display(skip)]
+        _hidden: fn(),
+    }
+
+    assert_eq!(Wrapper(5).to_string(), "5");
+    assert_eq!(Wrapper("crab").to_string(), "crab");
+    let entry = Entry {
+        value: vec![1],
+        hex: 255_u8,
+        label: "max",
+    };
+    assert_eq!(entry.to_string(), "[1] = 0xff (max)");
+    assert_eq!(Either::<u8, Vec<u8>>::Left(1).to_string(), "left 1");
+    assert_eq!(Either::<u8, Vec<u8>>::Right(vec![2]).to_string(), "Right([2])");
+    let partial = Partial {
+        value: [1],
+        _hidden: || {},
+    };
+    assert_eq!(partial.to_string(), "Partial { value: [1], .. }");
+}
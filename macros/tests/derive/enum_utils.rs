@@ -0,0 +1,90 @@
+use std_reset_macros::EnumUtils;
+
+#[test]
+fn unit_enum() {
+    #[derive(EnumUtils, Debug, PartialEq)]
+    enum Color {
+        Red,
+        DarkBlue,
+        r#Match,
+    }
+
+    assert_eq!(Color::COUNT, 3);
+    assert_eq!(Color::VARIANTS, [Color::Red, Color::DarkBlue, Color::Match]);
+    assert_eq!(
+        Color::iter().collect::<Vec<_>>(),
+        [&Color::Red, &Color::DarkBlue, &Color::Match]
+    );
+    assert_eq!(Color::DarkBlue.name(), "DarkBlue");
+    assert_eq!(Color::Match.name(), "Match");
+    assert!(Color::Red.is_red());
+    assert!(!Color::Red.is_dark_blue());
+    assert!(Color::Match.is_match());
+
+    assert_eq!("DarkBlue".parse::<Color>(), Ok(Color::DarkBlue));
+    let err = "dark_blue".parse::<Color>().unwrap_err();
+    assert_eq!(err, ParseColorError("dark_blue".to_string()));
+    assert_eq!(err.to_string(), "unknown variant `dark_blue` of `Color`");
+}
+
+#[test]
+fn case_style() {
+    #[derive(EnumUtils, Debug, PartialEq)]
+    #[enum_utils(case = "snake_case")]
+    enum Snake {
+        HttpServer,
+        IOError,
+        #[enum_utils(rename = "v2")]
+        Version2,
+    }
+
+    #[derive(EnumUtils, Debug, PartialEq)]
+    #[enum_utils(case = "SCREAMING_SNAKE_CASE")]
+    enum Screaming {
+        HttpServer,
+    }
+
+    #[derive(EnumUtils, Debug, PartialEq)]
+    #[enum_utils(case = "camelCase")]
+    enum Camel {
+        HttpServer,
+    }
+
+    assert_eq!(Snake::HttpServer.name(), "http_server");
+    assert_eq!(Snake::IOError.name(), "io_error");
+    assert_eq!(Snake::Version2.name(), "v2");
+    assert_eq!("io_error".parse::<Snake>(), Ok(Snake::IOError));
+    assert!("IOError".parse::<Snake>().is_err());
+    assert!(Snake::IOError.is_io_error());
+
+    assert_eq!(Screaming::HttpServer.name(), "HTTP_SERVER");
+    assert_eq!(Camel::HttpServer.name(), "httpServer");
+}
+
+#[test]
+fn data_enum() {
+    #[derive(EnumUtils, Debug, PartialEq)]
+    enum Shape<T> {
+        Circle(T),
+        Rect { width: T, height: T },
+        Empty,
+    }
+
+    let rect = Shape::Rect {
+        width: 2,
+        height: 3,
+    };
+    assert_eq!(Shape::<i32>::COUNT, 3);
+    assert_eq!(rect.name(), "Rect");
+    assert!(rect.is_rect());
+    assert!(!rect.is_empty());
+    assert_eq!(rect.as_rect(), Some((&2, &3)));
+    assert_eq!(rect.as_circle(), None);
+    assert_eq!(rect.into_rect(), Some((2, 3)));
+
+    let circle = Shape::Circle(String::from("r"));
+    assert_eq!(circle.as_circle().map(String::as_str), Some("r"));
+    assert_eq!(circle.into_circle(), Some(String::from("r")));
+
+    assert!(Shape::<()>::Empty.is_empty());
+}
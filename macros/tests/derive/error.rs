@@ -0,0 +1,180 @@
+use std::error::Error as _;
+use std::{fmt, io, num::ParseIntError};
+
+use std_reset_macros::Error;
+
+#[test]
+fn enum_() {
+    #[derive(Error, Debug)]
+    enum ConfigError {
+        #[display("failed to read {0}")]
+        Io(String, #[source] io::Error),
+        #[display("invalid port {port}")]
+        Port {
+            port: String,
+            #[source]
+            source: ParseIntError,
+        },
+        #[display("config is empty")]
+        Empty,
+        Unknown,
+    }
+
+    let err = ConfigError::Io("config.toml".to_string(), io::Error::other("not found"));
+    assert_eq!(err.to_string(), "failed to read config.toml");
+    assert_eq!(err.source().unwrap().to_string(), "not found");
+
+    let source = "x".parse::<u16>().unwrap_err();
+    let err = ConfigError::Port {
+        port: "x".to_string(),
+        source: source.clone(),
+    };
+    assert_eq!(err.to_string(), "invalid port x");
+    assert_eq!(err.source().unwrap().to_string(), source.to_string());
+
+    assert_eq!(ConfigError::Empty.to_string(), "config is empty");
+    assert!(ConfigError::Empty.source().is_none());
+    assert_eq!(ConfigError::Unknown.to_string(), "Unknown");
+}
+
+#[test]
+fn from() {
+    #[derive(Error, Debug)]
+    enum AppError {
+        #[display("parse error")]
+        Parse(#[from] ParseIntError),
+        #[display("io error")]
+        Io {
+            #[from]
+            source: io::Error,
+        },
+    }
+
+    #[derive(Error, Debug)]
+    #[display("wrapped")]
+    struct Wrapped(#[from] AppError);
+
+    fn parse(s: &str) -> Result<i32, AppError> {
+        Ok(s.parse::<i32>()?)
+    }
+
+    let err = parse("x").unwrap_err();
+    assert!(matches!(err, AppError::Parse(_)));
+    assert_eq!(err.to_string(), "parse error");
+    assert!(err.source().unwrap().is::<ParseIntError>());
+
+    let err = AppError::from(io::Error::other("io"));
+    assert!(matches!(err, AppError::Io { .. }));
+
+    let err = Wrapped::from(err);
+    assert_eq!(err.to_string(), "wrapped");
+    assert_eq!(err.source().unwrap().to_string(), "io error");
+}
+
+#[test]
+fn transparent() {
+    #[derive(Error, Debug)]
+    #[display("inner")]
+    struct Inner(#[source] io::Error);
+
+    #[derive(Error, Debug)]
+    enum Outer {
+        #[error(transparent)]
+        Inner(#[from] Inner),
+        #[error(transparent)]
+        Other { error: fmt::Error },
+    }
+
+    #[derive(Error, Debug)]
+    #[error(transparent)]
+    struct Public(Outer);
+
+    let err = Public(Outer::from(Inner(io::Error::other("io"))));
+    assert_eq!(err.to_string(), "inner");
+    assert_eq!(err.source().unwrap().to_string(), "io");
+
+    let err = Outer::Other { error: fmt::Error };
+    assert_eq!(err.to_string(), fmt::Error.to_string());
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn generic() {
+    #[derive(Error, Debug)]
+    #[display("{context}")]
+    struct Context<E> {
+        context: &'static str,
+        #[source]
+        error: E,
+    }
+
+    let err = Context {
+        context: "reading",
+        error: io::Error::other("io"),
+    };
+    assert_eq!(err.to_string(), "reading");
+    assert_eq!(err.source().unwrap().to_string(), "io");
+}
+
+#[test]
+fn boxed_source() {
+    #[derive(Error, Debug)]
+    #[display("request failed")]
+    struct Request {
+        #[source]
+        inner: Box<dyn std::error::Error + Send + Sync>,
+    }
+
+    #[derive(Error, Debug)]
+    enum Task {
+        #[display("task failed")]
+        Failed(#[from] Box<dyn std::error::Error>),
+        #[error(transparent)]
+        Request(Box<dyn std::error::Error + Send + Sync>),
+    }
+
+    let err = Request {
+        inner: "timeout".into(),
+    };
+    assert_eq!(err.to_string(), "request failed");
+    assert_eq!(err.source().unwrap().to_string(), "timeout");
+
+    let err = Task::from(Box::<dyn std::error::Error>::from("crashed"));
+    assert_eq!(err.to_string(), "task failed");
+    assert_eq!(err.source().unwrap().to_string(), "crashed");
+
+    let err = Task::Request(Box::new(Request {
+        inner: "timeout".into(),
+    }));
+    assert_eq!(err.to_string(), "request failed");
+    assert_eq!(err.source().unwrap().to_string(), "timeout");
+}
+
+#[test]
+fn custom_display() {
+    #[derive(Error, Debug)]
+    struct Custom<T> {
+        code: T,
+        #[source]
+        source: io::Error,
+    }
+
+    impl<T: fmt::Display> fmt::Display for Custom<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "code {}", self.code)
+        }
+    }
+
+    let err = Custom {
+        code: 7,
+        source: io::Error::other("io"),
+    };
+    assert_eq!(err.to_string(), "code 7");
+    assert_eq!(err.source().unwrap().to_string(), "io");
+
+    #[derive(std_reset_macros::Display, Error, Debug)]
+    struct Derived(u8);
+
+    assert_eq!(Derived(1).to_string(), "Derived(1)");
+    assert!(Derived(1).source().is_none());
+}
@@ -0,0 +1,152 @@
+use std::any::Any;
+
+use std_reset_macros::Fields;
+
+#[test]
+fn named_structure() {
+    #[derive(Fields)]
+    struct Settings {
+        font_size: u8,
+        theme: String,
+        r#type: Option<i32>,
+    }
+
+    assert_eq!(Settings::FIELD_NAMES, ["font_size", "theme", "type"]);
+    assert_eq!(
+        SettingsField::ALL,
+        [
+            SettingsField::FontSize,
+            SettingsField::Theme,
+            SettingsField::Type
+        ]
+    );
+    assert_eq!(SettingsField::Type.name(), "type");
+    assert_eq!(
+        SettingsField::from_name("font_size"),
+        Some(SettingsField::FontSize)
+    );
+    assert_eq!(SettingsField::from_name("size"), None);
+
+    let mut settings = Settings {
+        font_size: 14,
+        theme: "dark".to_string(),
+        r#type: None,
+    };
+    assert_eq!(
+        settings.get_dyn("font_size").unwrap().downcast_ref::<u8>(),
+        Some(&14)
+    );
+    assert!(settings.get_dyn("size").is_none());
+
+    *settings
+        .get_dyn_mut("font_size")
+        .unwrap()
+        .downcast_mut::<u8>()
+        .unwrap() = 16;
+    assert_eq!(settings.font_size, 16);
+
+    settings.set_dyn("type", Box::new(Some(1))).unwrap();
+    assert_eq!(settings.r#type, Some(1));
+
+    let value = settings.set_dyn("theme", Box::new(1)).unwrap_err();
+    assert_eq!(value.downcast_ref::<i32>(), Some(&1));
+    assert!(settings.set_dyn("size", Box::new(1)).is_err());
+}
+
+#[test]
+fn unnamed_structure() {
+    #[derive(Fields)]
+    struct Pair(i32, &'static str);
+
+    assert_eq!(Pair::FIELD_NAMES, ["0", "1"]);
+    assert_eq!(PairField::ALL, [PairField::Field0, PairField::Field1]);
+
+    let mut pair = Pair(1, "a");
+    pair.set_dyn("1", Box::new("b")).unwrap();
+    assert_eq!(pair.1, "b");
+}
+
+#[test]
+fn visitor() {
+    #[derive(Fields)]
+    struct Wrapper<T> {
+        value: T,
+        count: usize,
+    }
+
+    struct Counter(Vec<&'static str>);
+
+    impl std_reset::traits::fields::Visitor for Counter {
+        fn visit(&mut self, name: &'static str, value: &dyn Any) {
+            if value.is::<usize>() {
+                self.0.push(name);
+            }
+        }
+    }
+
+    let mut wrapper = Wrapper {
+        value: 1.5_f32,
+        count: 2,
+    };
+    let mut counter = Counter(vec![]);
+    wrapper.visit(&mut counter);
+    assert_eq!(counter.0, ["count"]);
+
+    wrapper.visit_mut(&mut |_: &'static str, value: &mut dyn Any| {
+        if let Some(value) = value.downcast_mut::<f32>() {
+            *value *= 2.0;
+        }
+    });
+    assert_eq!(wrapper.value, 3.0);
+}
+
+#[test]
+fn selection_and_rename() {
+    #[derive(Fields)]
+    struct Request {
+        #[std_reset(rename = "url")]
+        path: String,
+        #[exclude_fields]
+        token: String,
+        #[std_reset(rename = "retryCount")]
+        retries: u8,
+    }
+
+    assert_eq!(Request::FIELD_NAMES, ["url", "retryCount"]);
+    assert_eq!(
+        RequestField::ALL,
+        [RequestField::Url, RequestField::RetryCount]
+    );
+    assert_eq!(RequestField::from_name("url"), Some(RequestField::Url));
+    assert_eq!(RequestField::from_name("path"), None);
+
+    let mut request = Request {
+        path: "/".to_string(),
+        token: "secret".to_string(),
+        retries: 0,
+    };
+    assert!(request.get_dyn("token").is_none());
+    request.set_dyn("retryCount", Box::new(3_u8)).unwrap();
+    assert_eq!(
+        (
+            request.path.as_str(),
+            request.token.as_str(),
+            request.retries
+        ),
+        ("/", "secret", 3)
+    );
+
+    #[derive(Fields)]
+    struct Only {
+        #[include_fields]
+        id: u32,
+        label: &'static str,
+    }
+
+    assert_eq!(Only::FIELD_NAMES, ["id"]);
+    assert_eq!(OnlyField::ALL, [OnlyField::Id]);
+    let only = Only { id: 1, label: "a" };
+    assert_eq!(only.get_dyn("id").unwrap().downcast_ref::<u32>(), Some(&1));
+    assert!(only.get_dyn("label").is_none());
+    assert_eq!(only.label, "a");
+}
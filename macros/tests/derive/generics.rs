@@ -0,0 +1,244 @@
+//! Матрица компиляции: каждый набор derive-макросов применяется к структурам и перечислениям
+//! с лифтаймами, константными параметрами, параметрами по умолчанию и сложными `where`.
+
+/// Объявляет модуль `$name` со структурами всех видов параметров, для которых выводятся `$derive`.
+///
+/// После `;` можно указать атрибуты всех структур и виды структур: `named`, `phantom`, `tuple`
+/// и `unit`, если макрос поддерживает не все из них.
+macro_rules! structs {
+    ($name:ident: $($derive:path),+) => {
+        structs!($name: $($derive),+; named, phantom, tuple, unit);
+    };
+    ($name:ident: $($derive:path),+; $(#[$attr:meta])* $($shape:ident),+) => {
+        mod $name {
+            #![allow(dead_code)]
+            use std::fmt::Debug;
+            use std_reset::prelude::*;
+            #[allow(unused_imports)]
+            use std_reset::prelude::Default;
+
+            structs!(@shapes [$($derive),+] [$(#[$attr])*] $($shape)+);
+        }
+    };
+    (@shapes $derives:tt $attrs:tt $shape:ident $($rest:ident)*) => {
+        structs!(@$shape $derives $attrs);
+        structs!(@shapes $derives $attrs $($rest)*);
+    };
+    (@shapes $derives:tt $attrs:tt) => {};
+    (@named [$($derive:path),+] [$($attr:tt)*]) => {
+        #[derive($($derive),+)]
+        $($attr)*
+        pub struct Lifetimes<'a, 'b: 'a, T: ?Sized + Debug + PartialEq + 'b>
+        where
+            &'b T: Clone,
+        {
+            pub name: &'a str,
+            pub items: Vec<&'b T>,
+        }
+
+        #[derive($($derive),+)]
+        $($attr)*
+        pub struct Consts<T: Debug + Clone + PartialEq, const N: usize, const M: usize = 2> {
+            pub values: [T; N],
+            pub bytes: [u8; M],
+        }
+
+        #[derive($($derive),+)]
+        $($attr)*
+        pub struct Defaults<T: Debug + Clone + PartialEq = String, U = u8>
+        where
+            U: Debug + Clone + PartialEq,
+        {
+            pub first: T,
+            pub second: U,
+        }
+
+        #[derive($($derive),+)]
+        $($attr)*
+        pub struct Wheres<'a, T, U: 'a>
+        where
+            T: Debug + Clone + PartialEq + Into<U>,
+            U: Debug + PartialEq,
+            for<'x> &'x T: IntoIterator,
+        {
+            pub value: T,
+            pub target: Option<&'a U>,
+        }
+    };
+    (@phantom [$($derive:path),+] [$($attr:tt)*]) => {
+        #[derive($($derive),+)]
+        $($attr)*
+        pub struct Phantom<'a, T: Debug, const N: usize> {
+            #[std_reset(skip)]
+            pub marker: std::marker::PhantomData<&'a [T; N]>,
+            pub count: usize,
+        }
+    };
+    (@tuple [$($derive:path),+] [$($attr:tt)*]) => {
+        #[derive($($derive),+)]
+        $($attr)*
+        pub struct Tuple<'a, T: Debug + PartialEq, const N: usize>(pub &'a [T; N], pub usize)
+        where
+            T: Clone;
+    };
+    (@unit [$($derive:path),+] [$($attr:tt)*]) => {
+        #[derive($($derive),+)]
+        $($attr)*
+        pub struct Unit<const N: usize = 0>;
+    };
+}
+
+/// Объявляет модуль `$name` с перечислением всех видов параметров, для которого выводятся `$derive`.
+macro_rules! enums {
+    ($name:ident: $($derive:path),+) => {
+        mod $name {
+            #![allow(dead_code)]
+            use std::fmt::Debug;
+            use std_reset::prelude::*;
+            #[allow(unused_imports)]
+            use std_reset::prelude::Default;
+
+            #[derive($($derive),+)]
+            pub enum Choice<'a, T: Debug + Clone = u8, const N: usize = 2>
+            where
+                T: PartialEq,
+            {
+                #[default]
+                Empty,
+                One { value: &'a T, count: usize },
+                Many { values: [T; N], count: usize },
+            }
+        }
+    };
+}
+
+structs!(structs_default: Default, Reset, New, Debug, Clone, PartialEq);
+structs!(structs_accessors: Accessors, Lens, Fields, Patch, Debug);
+structs!(structs_display: Display, Debug);
+structs!(structs_error: Display, Error, Debug);
+structs!(structs_soa: Soa, Debug; named, tuple);
+structs!(structs_preset: Preset, Debug; #[preset(name = "preset")] named, phantom);
+enums!(enums_default: Default, Accessors, EnumUtils, Display, Debug);
+enums!(enums_error: Default, Display, Error, Debug);
+
+mod wrappers {
+    #![allow(dead_code)]
+    use std::fmt::Debug;
+    use std_reset::prelude::*;
+
+    #[derive(Deref, Newtype, Debug)]
+    pub struct Items<'a, T: Debug = u8, const N: usize = 2>(pub Vec<&'a [T; N]>)
+    where
+        T: Clone;
+
+    #[derive(Deref, Debug)]
+    pub struct Named<'a, T: ?Sized + 'a, const N: usize>
+    where
+        T: Debug,
+    {
+        #[deref]
+        pub value: Box<T>,
+        pub extra: &'a [u8; N],
+    }
+
+    #[derive(Deref)]
+    pub enum Either<'a, T: ?Sized = str, const N: usize = 1>
+    where
+        T: Debug,
+    {
+        Borrowed(&'a T),
+        Tagged {
+            tag: [u8; N],
+            #[deref]
+            value: &'a T,
+        },
+    }
+
+    #[derive(Ops, Debug, Clone, Copy, PartialEq)]
+    pub struct Vector<T = i32, const N: usize = 2>(pub [T; N])
+    where
+        T: Copy;
+
+    #[derive(Ops, Debug, Clone, Copy, PartialEq)]
+    pub struct Point<T: Copy = f64> {
+        pub x: T,
+        pub y: T,
+    }
+}
+
+#[test]
+fn generic_values() {
+    use std_reset::prelude::{Lens, To};
+
+    let consts = structs_default::Consts::<u8, 3>::default();
+    assert_eq!(consts.values, [0; 3]);
+    assert_eq!(consts.bytes, [0; 2]);
+
+    let defaults: structs_default::Defaults = structs_default::Defaults::new("a".to_string(), 1);
+    assert_eq!(defaults.second, 1);
+
+    let lifetimes = structs_accessors::Lifetimes::<str> {
+        name: "items",
+        items: vec!["a", "b"],
+    };
+    assert_eq!(*lifetimes.get_name(), "items");
+    let items = structs_accessors::Lifetimes::lens_items();
+    assert_eq!(items.get(&lifetimes).len(), 2);
+
+    let tuple = structs_accessors::Tuple(&[1, 2], 2);
+    let other = structs_accessors::Tuple(&[1, 2], 3);
+    let patch = tuple.diff(&other);
+    assert!(patch.0.is_none());
+    assert_eq!(patch.1, Some(3));
+
+    let phantom = structs_accessors::Phantom::<u8, 2> {
+        marker: std::marker::PhantomData,
+        count: 1,
+    };
+    let mut other = structs_accessors::Phantom {
+        count: 2,
+        ..phantom
+    };
+    let patch = phantom.diff(&other);
+    assert_eq!(patch.count, Some(2));
+    other.apply(structs_accessors::PhantomPatch {
+        count: Some(3),
+        ..Default::default()
+    });
+    assert_eq!(other.count, 3);
+
+    let soa = vec![
+        structs_soa::Tuple(&[1, 2], 2),
+        structs_soa::Tuple(&[3, 4], 4),
+    ]
+    .to::<structs_soa::TupleVec<i32, 2>>();
+    assert_eq!(soa.column_1(), [2, 4]);
+    assert_eq!(**soa.get(1).unwrap().0, [3, 4]);
+
+    let preset = structs_preset::Consts::<u8, 3>::preset();
+    assert_eq!(preset.values, [0; 3]);
+    let preset: structs_preset::Defaults = structs_preset::Defaults::preset();
+    assert_eq!((preset.first.as_str(), preset.second), ("", 0));
+
+    let choice: enums_default::Choice = enums_default::Choice::One {
+        value: &1,
+        count: 2,
+    };
+    assert_eq!(choice.get_count(), Some(&2));
+    assert_eq!(choice.name(), "One");
+    assert!(enums_default::Choice::<u8, 3>::default().is_empty());
+
+    let vector = wrappers::Vector([1, 2]) + wrappers::Vector([3, 4]);
+    assert_eq!(vector, wrappers::Vector([4, 6]));
+    let point = wrappers::Point { x: 1.0, y: 2.0 } * 2.0;
+    assert_eq!(point, wrappers::Point { x: 2.0, y: 4.0 });
+
+    let either: wrappers::Either = wrappers::Either::Tagged {
+        tag: [1],
+        value: "tagged",
+    };
+    assert_eq!(either.len(), 6);
+
+    let items = wrappers::Items(vec![&[1_u8, 2]]);
+    assert_eq!(items.len(), 1);
+}
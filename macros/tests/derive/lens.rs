@@ -0,0 +1,75 @@
+use std_reset::prelude::Lens;
+
+#[test]
+fn nested_lenses() {
+    #[derive(Lens, Clone, Debug, PartialEq)]
+    struct Address {
+        city: String,
+        zip: u32,
+    }
+
+    #[derive(Lens, Clone, Debug, PartialEq)]
+    struct User {
+        name: String,
+        address: Address,
+    }
+
+    #[derive(Lens, Clone, Debug, PartialEq)]
+    struct State {
+        user: User,
+        #[exclude_lens]
+        version: u32,
+    }
+
+    let state = State {
+        user: User {
+            name: "Ferris".to_string(),
+            address: Address {
+                city: "Berlin".to_string(),
+                zip: 10115,
+            },
+        },
+        version: 1,
+    };
+
+    let city = State::lens_user()
+        .then(User::lens_address())
+        .then(Address::lens_city());
+    assert_eq!(city.get(&state), "Berlin");
+
+    let moved = city.set(state.clone(), "Paris".to_string());
+    assert_eq!(moved.user.address.city, "Paris");
+    assert_eq!(moved.user.name, state.user.name);
+
+    let zip = State::lens_user()
+        .then(User::lens_address())
+        .then(Address::lens_zip());
+    let moved = zip.modify(moved, |zip| zip + 1);
+    assert_eq!(moved.user.address.zip, 10116);
+
+    let mut state = moved;
+    *city.get_mut(&mut state) = "Rome".to_string();
+    assert_eq!(state.user.address.city, "Rome");
+    assert_eq!(state.version, 1);
+}
+
+#[test]
+fn generic_and_tuple() {
+    #[derive(Lens, Debug, PartialEq)]
+    struct Pair<T>(T, T);
+
+    #[derive(Lens, Debug, PartialEq)]
+    struct Wrapper<'a, T> {
+        label: &'a str,
+        pair: Pair<T>,
+    }
+
+    let second = Wrapper::lens_pair().then(Pair::lens_1());
+    let wrapper = Wrapper {
+        label: "pair",
+        pair: Pair(1, 2),
+    };
+    let wrapper = second.modify(wrapper, |value| value * 10);
+    assert_eq!(wrapper.pair, Pair(1, 20));
+    assert_eq!(*Wrapper::lens_label().get(&wrapper), "pair");
+}
@@ -1,4 +1,17 @@
+mod as_prim;
 mod default;
 mod deref;
+mod enum_utils;
+mod error;
+mod fields;
+mod generics;
+mod lens;
+mod newtype;
+mod ops;
+mod patch;
+mod preset;
+mod reset;
 mod setter_getter;
+mod soa;
+mod std_reset;
 mod display;
\ No newline at end of file
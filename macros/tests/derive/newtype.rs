@@ -0,0 +1,80 @@
+use std::borrow::Borrow;
+
+use std_reset_macros::{Deref, Newtype};
+
+#[test]
+fn newtype_delegation() {
+    #[derive(Deref, Newtype, Debug, PartialEq)]
+    struct Ids(Vec<u32>);
+
+    let mut ids: Ids = vec![2, 1].into_iter().collect();
+    ids.as_mut().push(3);
+
+    let borrowed: &Vec<u32> = ids.borrow();
+    assert_eq!(borrowed, &vec![2, 1, 3]);
+    assert_eq!(ids.as_ref(), &vec![2, 1, 3]);
+    assert_eq!(Ids::from(vec![1]), Ids(vec![1]));
+
+    let mut iterated = vec![];
+    for id in &ids {
+        iterated.push(*id);
+    }
+    assert_eq!(iterated, vec![2, 1, 3]);
+    assert_eq!(ids.into_iter().max(), Some(3));
+}
+
+#[test]
+fn newtype_with_generics_and_skips() {
+    #[derive(Newtype, Debug, PartialEq)]
+    #[newtype(skip(into_iterator, from_iterator))]
+    struct Wrapper<T>(T);
+
+    let wrapper: Wrapper<i32> = 1.into();
+    assert_eq!(wrapper.as_ref(), &1);
+
+    #[derive(Newtype, Debug, PartialEq)]
+    #[newtype(skip(from, from_iterator))]
+    struct Named<T> {
+        label: &'static str,
+        #[deref]
+        items: Vec<T>,
+    }
+
+    let named = Named {
+        label: "crab",
+        items: vec!['a', 'b'],
+    };
+    assert_eq!((&named).into_iter().collect::<String>(), "ab");
+    assert_eq!(Vec::from(named), vec!['a', 'b']);
+}
+
+#[test]
+fn newtype_scalar() {
+    #[derive(Newtype, Debug, PartialEq)]
+    struct Meters(pub f64);
+
+    let meters = Meters::from(1.5);
+    assert_eq!(meters.as_ref(), &1.5);
+    assert_eq!(f64::from(meters), 1.5);
+}
+
+#[test]
+fn newtype_generic() {
+    #[derive(Newtype, Debug, PartialEq)]
+    struct Wrap<T>(pub T);
+
+    let wrap = Wrap::from(3_u8);
+    assert_eq!(wrap.as_ref(), &3);
+    assert_eq!(Wrap::from(vec![1]).as_ref(), &vec![1]);
+}
+
+#[test]
+fn newtype_explicit_iteration() {
+    #[derive(Newtype, Debug, PartialEq)]
+    #[newtype(iter)]
+    struct Pair([u8; 2]);
+
+    let pair = Pair([1, 2]);
+    assert_eq!((&pair).into_iter().count(), 2);
+    assert_eq!(pair.into_iter().collect::<Vec<_>>(), [1, 2]);
+}
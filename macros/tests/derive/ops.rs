@@ -0,0 +1,68 @@
+use std_reset_macros::Ops;
+
+#[test]
+fn newtype_forwarding() {
+    #[derive(Ops, Debug, PartialEq, Clone, Copy)]
+    #[ops(add, sub, mul, div, rem, neg)]
+    struct Meters(f64);
+
+    assert_eq!(Meters(1.5) + Meters(2.0), Meters(3.5));
+    assert_eq!(Meters(1.5) - Meters(2.0), Meters(-0.5));
+    assert_eq!(Meters(1.5) * Meters(2.0), Meters(3.0));
+    assert_eq!(Meters(3.0) / Meters(2.0), Meters(1.5));
+    assert_eq!(Meters(3.0) % Meters(2.0), Meters(1.0));
+    assert_eq!(-Meters(3.0), Meters(-3.0));
+
+    let mut meters = Meters(1.0);
+    meters += Meters(2.0);
+    meters *= Meters(3.0);
+    assert_eq!(meters, Meters(9.0));
+
+    #[derive(Ops, Debug, PartialEq)]
+    struct Wrapper<T>(T);
+    assert_eq!(Wrapper(2_u8) * Wrapper(3), Wrapper(6));
+}
+
+#[test]
+fn element_wise_and_scalar() {
+    #[derive(Ops, Debug, PartialEq)]
+    struct Vector<T>([T; 2]);
+
+    assert_eq!(Vector([1, 2]) + Vector([3, 4]), Vector([4, 6]));
+    assert_eq!(Vector([1.0, 2.0]) * 0.5, Vector([0.5, 1.0]));
+    let mut vector = Vector([1, 2]);
+    vector *= 3;
+    vector -= Vector([1, 1]);
+    assert_eq!(vector, Vector([2, 5]));
+
+    #[derive(Ops, Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let mut point = Point { x: 4, y: 6 } - Point { x: 1, y: 2 };
+    point /= 3;
+    point += Point { x: 1, y: 1 };
+    assert_eq!(point, Point { x: 2, y: 2 });
+
+    #[derive(Ops, Debug, PartialEq)]
+    struct Mixed {
+        count: u32,
+        values: [f32; 3],
+    }
+    let mixed = Mixed {
+        count: 1,
+        values: [1.0, 2.0, 3.0],
+    } * Mixed {
+        count: 2,
+        values: [2.0, 2.0, 2.0],
+    };
+    assert_eq!(
+        mixed,
+        Mixed {
+            count: 2,
+            values: [2.0, 4.0, 6.0]
+        }
+    );
+}
@@ -0,0 +1,111 @@
+use std_reset_macros::Patch;
+
+#[test]
+fn named_structure() {
+    #[derive(Patch, Debug, Clone, PartialEq)]
+    #[patch(derive(Debug, Clone, PartialEq))]
+    struct State {
+        name: String,
+        count: u32,
+        #[exclude_patch]
+        cache: Vec<u8>,
+    }
+
+    let old = State {
+        name: "crab".to_string(),
+        count: 1,
+        cache: vec![1],
+    };
+    let new = State {
+        name: "ferris".to_string(),
+        count: 1,
+        cache: vec![2],
+    };
+
+    let patch = old.diff(&new);
+    assert_eq!(
+        patch,
+        StatePatch {
+            name: Some("ferris".to_string()),
+            count: None,
+        }
+    );
+    assert!(!patch.is_empty());
+    assert!(old.diff(&old).is_empty());
+    assert!(StatePatch::default().is_empty());
+
+    let mut state = old.clone();
+    state.apply(patch);
+    assert_eq!(state.name, "ferris");
+    assert_eq!(state.cache, vec![1]);
+}
+
+#[test]
+fn unnamed_structure() {
+    #[derive(Patch, Debug, Clone, PartialEq)]
+    struct Pair(#[exclude_patch] u8, i32, String);
+
+    let mut pair = Pair(0, 1, "a".to_string());
+    let patch = pair.diff(&Pair(9, 2, "a".to_string()));
+    assert_eq!(patch.0, Some(2));
+    assert_eq!(patch.1, None);
+
+    pair.apply(patch);
+    assert_eq!(pair, Pair(0, 2, "a".to_string()));
+}
+
+#[test]
+fn include() {
+    #[derive(Patch)]
+    struct State {
+        #[include_patch]
+        count: u32,
+        name: String,
+    }
+
+    let old = State {
+        count: 1,
+        name: "a".to_string(),
+    };
+    let new = State {
+        count: 1,
+        name: "b".to_string(),
+    };
+    let StatePatch { count } = old.diff(&new);
+    assert!(count.is_none());
+    assert_eq!(new.name, "b");
+}
+
+#[test]
+fn nested_and_generic() {
+    #[derive(Patch, Debug, Clone, PartialEq)]
+    struct Point<T> {
+        x: T,
+        y: T,
+    }
+
+    #[derive(Patch, Debug, Clone, PartialEq)]
+    struct Shape {
+        #[patch(nested)]
+        center: Point<f32>,
+        radius: u32,
+    }
+
+    let mut shape = Shape {
+        center: Point { x: 0.0, y: 0.0 },
+        radius: 1,
+    };
+    let moved = Shape {
+        center: Point { x: 0.0, y: 5.0 },
+        radius: 1,
+    };
+
+    let patch = shape.diff(&moved);
+    let center = patch.center.as_ref().unwrap();
+    assert_eq!((center.x, center.y), (None, Some(5.0)));
+    assert!(patch.radius.is_none());
+    assert!(shape.diff(&shape).center.is_none());
+
+    shape.apply(patch);
+    assert_eq!(shape, moved);
+}
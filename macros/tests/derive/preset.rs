@@ -0,0 +1,63 @@
+use std_reset_macros::{Default, Preset};
+
+#[test]
+fn presets() {
+    #[derive(Default, Preset, Debug, PartialEq)]
+    #[preset(name = "dev", port = 8080, debug = true)]
+    #[preset(name = "prod", host = "example.com".to_string(), r#name = "prod")]
+    #[preset(name = "local")]
+    #[preset(fn = "named", name = "named")]
+    struct Config {
+        #[default("localhost".to_string())]
+        host: String,
+        #[default(80)]
+        port: u16,
+        debug: bool,
+        #[std_reset(default = "app")]
+        r#name: &'static str,
+    }
+
+    let dev = Config::dev();
+    assert_eq!(dev.host, "localhost");
+    assert_eq!(dev.port, 8080);
+    assert!(dev.debug);
+    assert_eq!(dev.r#name, "app");
+
+    let prod = Config::prod();
+    assert_eq!(prod.host, "example.com");
+    assert_eq!(prod.port, 80);
+    assert!(!prod.debug);
+    assert_eq!(prod.r#name, "prod");
+
+    assert_eq!(Config::local(), Config::default());
+
+    let named = Config::named();
+    assert_eq!((named.r#name, named.port), ("named", 80));
+}
+
+#[test]
+fn generic_presets() {
+    #[derive(Preset, Debug, PartialEq)]
+    #[preset(name = "scaled", scale = 2)]
+    #[preset(name = "empty")]
+    struct Point<T> {
+        value: T,
+        #[default(1)]
+        scale: i32,
+    }
+
+    assert_eq!(
+        Point::<String>::empty(),
+        Point {
+            value: String::new(),
+            scale: 1
+        }
+    );
+    assert_eq!(
+        Point::<Vec<u8>>::scaled(),
+        Point {
+            value: vec![],
+            scale: 2
+        }
+    );
+}
@@ -0,0 +1,99 @@
+use std_reset_macros::{Default, Reset};
+
+#[test]
+fn named_structure() {
+    #[derive(Default, Reset, Debug, PartialEq)]
+    struct Settings {
+        #[default(14)]
+        font_size: u8,
+        #[default("dark".to_string())]
+        theme: String,
+        zoom: f32,
+        #[default(Some("en"))]
+        r#type: Option<&'static str>,
+    }
+
+    let mut settings = Settings {
+        font_size: 20,
+        theme: "light".to_string(),
+        zoom: 1.5,
+        r#type: None,
+    };
+    assert!(!settings.is_default_font_size());
+    assert!(!settings.is_default_type());
+
+    settings.reset_font_size();
+    settings.reset_type();
+    assert_eq!(settings.font_size, 14);
+    assert_eq!(settings.r#type, Some("en"));
+    assert!(settings.is_default_font_size());
+    assert!(!settings.is_default_theme());
+    assert!(!settings.is_default_zoom());
+
+    settings.reset();
+    assert_eq!(settings, Settings::default());
+}
+
+#[test]
+fn unnamed_structure() {
+    #[derive(Reset, Debug, PartialEq)]
+    struct Pair(#[default(1)] i32, String);
+
+    let mut pair = Pair(5, "pair".to_string());
+    pair.reset_1();
+    assert_eq!(pair, Pair(5, String::new()));
+    assert!(pair.is_default_1());
+    assert!(!pair.is_default_0());
+
+    pair.reset();
+    assert_eq!(pair, Pair(1, String::new()));
+}
+
+#[test]
+fn with_generics() {
+    struct NoPartialEq;
+
+    #[derive(Reset)]
+    struct Wrapper<T> {
+        value: Option<T>,
+        #[default(3)]
+        count: usize,
+    }
+
+    let mut wrapper = Wrapper {
+        value: Some(NoPartialEq),
+        count: 0,
+    };
+    wrapper.reset();
+    assert!(wrapper.value.is_none());
+    assert!(wrapper.is_default_count());
+
+    let wrapper = Wrapper {
+        value: Some(1),
+        count: 3,
+    };
+    assert!(!wrapper.is_default_value());
+}
+
+#[test]
+fn field_without_partial_eq() {
+    #[derive(Debug)]
+    struct NoEq(u8);
+
+    #[derive(Reset)]
+    struct Cache {
+        #[default(NoEq(1))]
+        entry: NoEq,
+        #[default(3)]
+        hits: u32,
+    }
+
+    let mut cache = Cache {
+        entry: NoEq(5),
+        hits: 7,
+    };
+    assert!(!cache.is_default_hits());
+    cache.reset();
+    assert_eq!(cache.entry.0, 1);
+    assert!(cache.is_default_hits());
+}
@@ -9,7 +9,7 @@ fn setter_and_getter_default() {
     }
 
     let mut tmp = Tmp::default().set_first(2).set_second(2);
-    tmp.set_second(5);
+    tmp = tmp.set_second(5);
 
     assert_eq!(
         tmp,
@@ -19,8 +19,8 @@ fn setter_and_getter_default() {
         }
     );
 
-    assert_eq!(tmp.get_first(), 2);
-    assert_eq!(tmp.get_second(), 5);
+    assert_eq!(*tmp.get_first(), 2);
+    assert_eq!(*tmp.get_second(), 5);
 }
 
 #[test]
@@ -47,7 +47,7 @@ fn setter_and_getter_with_exclude_fields() {
         }
     );
 
-    assert_eq!(tmp.get_third(), 0);
+    assert_eq!(*tmp.get_third(), 0);
 }
 
 #[test]
@@ -73,5 +73,319 @@ fn setter_and_getter_with_include_field() {
         }
     );
 
+    assert_eq!(*tmp.get_second(), 2);
+}
+
+#[test]
+fn setter_modes() {
+    #[derive(Setter, Default, PartialEq, Debug)]
+    struct Owned {
+        first: String,
+        second: Vec<i32>,
+    }
+
+    assert_eq!(
+        Owned::default()
+            .set_first("crab".to_string())
+            .set_second(vec![1, 2]),
+        Owned {
+            first: "crab".to_string(),
+            second: vec![1, 2]
+        }
+    );
+
+    #[derive(Setter, Default, PartialEq, Debug)]
+    #[setter(mode = "mut_ref")]
+    struct MutRef {
+        first: String,
+        #[setter(mode = "plain")]
+        second: Vec<i32>,
+    }
+
+    let mut tmp = MutRef::default();
+    tmp.set_first("crab".to_string())
+        .set_first("ferris".to_string());
+    let () = tmp.set_second(vec![1, 2]);
+
+    assert_eq!(
+        tmp,
+        MutRef {
+            first: "ferris".to_string(),
+            second: vec![1, 2]
+        }
+    );
+}
+
+#[test]
+fn getter_modes() {
+    #[derive(Getter, Default)]
+    struct Tmp {
+        first: String,
+        #[getter(copy)]
+        second: i32,
+        #[getter(clone, mut)]
+        third: Vec<i32>,
+        #[getter(deref)]
+        fourth: String,
+        #[getter(deref)]
+        fifth: Vec<i32>,
+        #[getter(deref)]
+        sixth: Option<String>,
+    }
+
+    let mut tmp = Tmp {
+        first: "crab".to_string(),
+        second: 2,
+        fourth: "ferris".to_string(),
+        fifth: vec![1, 2],
+        sixth: Some("rust".to_string()),
+        ..Default::default()
+    };
+    tmp.third_mut().push(3);
+
+    assert_eq!(tmp.get_first(), &"crab".to_string());
     assert_eq!(tmp.get_second(), 2);
+    assert_eq!(tmp.get_third(), vec![3]);
+    assert_eq!(tmp.get_fourth(), "ferris");
+    assert_eq!(tmp.get_fifth(), &[1, 2]);
+    assert_eq!(tmp.get_sixth(), Some(&"rust".to_string()));
+
+    #[derive(Getter, Clone, Copy)]
+    #[getter(copy)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+    let point = Point { x: 1.0, y: 2.0 };
+    assert_eq!(point.get_x() + point.get_y(), 3.0);
+}
+
+#[test]
+fn naming_and_visibility() {
+    #[derive(Setter, Getter, Default, PartialEq, Debug)]
+    #[setter(prefix = "with_", mode = "owned")]
+    #[getter(prefix = "", vis = "pub(crate)")]
+    struct Tmp {
+        /// Documented field.
+        first: i32,
+        #[setter(name = "rename_second")]
+        #[getter(name = "second_field", mut)]
+        second: String,
+        #[setter(prefix = "put_", vis = "")]
+        #[getter(prefix = "read_", copy)]
+        third: u8,
+    }
+
+    let mut tmp = Tmp::default()
+        .with_first(1)
+        .rename_second("crab".to_string())
+        .put_third(3);
+    tmp.second_field_mut().push('s');
+
+    assert_eq!(*tmp.first(), 1);
+    assert_eq!(tmp.second_field(), "crabs");
+    assert_eq!(tmp.read_third(), 3);
+}
+
+#[test]
+fn tuple_structure() {
+    #[derive(Setter, Getter, Default, PartialEq, Debug)]
+    struct Tmp(
+        i32,
+        #[getter(deref)] String,
+        #[setter(name = "with_flag")]
+        #[getter(name = "flag", copy)]
+        bool,
+    );
+
+    let tmp = Tmp::default()
+        .set_0(1)
+        .set_1("crab".to_string())
+        .with_flag(true);
+
+    assert_eq!(tmp, Tmp(1, "crab".to_string(), true));
+    assert_eq!(*tmp.get_0(), 1);
+    assert_eq!(tmp.get_1(), "crab");
+    assert!(tmp.flag());
+}
+
+#[test]
+fn enum_() {
+    #[derive(Setter, Getter, PartialEq, Debug)]
+    #[getter(prefix = "")]
+    enum Message {
+        Text {
+            id: u32,
+            #[getter(mut)]
+            body: String,
+        },
+        Ping {
+            id: u32,
+        },
+        Close(#[getter(name = "code")] u32),
+    }
+
+    let mut text = Message::Text {
+        id: 1,
+        body: "crab".to_string(),
+    };
+    text.body_mut().unwrap().push('s');
+
+    assert_eq!(text.id(), Some(&1));
+    assert_eq!(text.body(), Some(&"crabs".to_string()));
+    assert_eq!(Message::Ping { id: 2 }.body(), None);
+    assert_eq!(Message::Close(3).id(), None);
+    assert_eq!(Message::Close(3).code(), Some(&3));
+
+    assert!(text.set_body("ferris".to_string()));
+    assert_eq!(text.body(), Some(&"ferris".to_string()));
+    let mut ping = Message::Ping { id: 2 };
+    assert!(!ping.set_body("crab".to_string()));
+    assert_eq!(ping, Message::Ping { id: 2 });
+    let mut close = Message::Close(3);
+    assert!(close.set_0(4));
+    assert!(!close.set_id(5));
+    assert_eq!(close, Message::Close(4));
+
+    #[derive(Setter, Getter, PartialEq, Debug)]
+    #[setter(mode = "plain")]
+    enum Id {
+        User(#[getter(copy)] u64),
+        Org(u64),
+    }
+
+    let mut id = Id::User(1);
+    id.set_0(2);
+    assert_eq!(id, Id::User(2));
+    assert_eq!(id.get_0(), 2);
+    assert_eq!(Id::Org(3).get_0(), 3);
+}
+
+#[test]
+fn setter_validate_on_change_and_into() {
+    use std::cell::RefCell;
+
+    #[derive(Debug, PartialEq)]
+    struct PortError(u16);
+
+    fn check_port(port: &u16) -> Result<(), PortError> {
+        if *port < 1024 {
+            Err(PortError(*port))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_host(host: &str) -> Result<(), &'static str> {
+        if host.is_empty() {
+            Err("empty host")
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(Setter, Default, Debug)]
+    #[setter(into)]
+    struct Config {
+        #[setter(validate = check_port, error = PortError)]
+        port: u16,
+        #[setter(validate = check_host, mode = "mut_ref")]
+        host: String,
+        #[setter(on_change = Self::log_name, mode = "plain")]
+        name: String,
+        #[exclude_setter]
+        changes: RefCell<Vec<(String, String)>>,
+    }
+
+    impl Config {
+        fn log_name(&self, old: &str, new: &str) {
+            self.changes.borrow_mut().push((old.to_string(), new.to_string()));
+        }
+    }
+
+    let config = Config::default().set_port(8080_u16).unwrap();
+    assert_eq!(config.port, 8080);
+    // при ошибке структура не теряется
+    let (mut config, err) = config.set_port(80_u16).unwrap_err();
+    assert_eq!(err, PortError(80));
+    assert_eq!(config.port, 8080);
+
+    config.set_host("localhost").unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.set_host("").unwrap_err().to_string(), "empty host");
+    assert_eq!(config.host, "localhost");
+
+    config.set_name("crab");
+    config.set_name("ferris");
+    assert_eq!(
+        *config.changes.borrow(),
+        vec![
+            (String::new(), "crab".to_string()),
+            ("crab".to_string(), "ferris".to_string())
+        ]
+    );
+}
+
+#[test]
+fn setter_collection() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Setter, Default, Debug)]
+    #[setter(mode = "mut_ref")]
+    struct Request {
+        #[setter(collection)]
+        headers: Vec<(String, String)>,
+        #[setter(collection)]
+        query: HashMap<String, String>,
+        #[setter(collection, mode = "owned")]
+        cookies: BTreeMap<String, u32>,
+        #[setter(collection)]
+        body: Option<String>,
+        #[exclude_setter]
+        _id: u32,
+    }
+
+    let mut request = Request::default();
+    request
+        .push_headers(("Host".into(), "localhost".into()))
+        .extend_headers([("Accept".into(), "*/*".into())])
+        .insert_query("page".into(), "2".into())
+        .set_body(Some("{}".into()));
+    assert_eq!(request.headers.len(), 2);
+    assert_eq!(request.remove_query("page"), Some("2".into()));
+    assert_eq!(request.remove_query("page"), None);
+    assert_eq!(request.take_body(), Some("{}".into()));
+    assert_eq!(request.body, None);
+    request.set_body(Some("[]".into())).clear_body().clear_headers();
+    assert_eq!(request.body, None);
+    assert!(request.headers.is_empty());
+
+    let request = request.insert_cookies("session".into(), 1);
+    assert_eq!(request.cookies.get("session"), Some(&1));
+
+    #[derive(Setter, Debug)]
+    #[setter(mode = "plain")]
+    enum Queue<T> {
+        Active {
+            #[setter(collection)]
+            items: Vec<T>,
+        },
+        Closed {
+            items: Vec<T>,
+        },
+    }
+
+    let mut queue = Queue::Active { items: vec![1] };
+    queue.push_items(2);
+    queue.extend_items(3..5);
+    let Queue::Active { items } = &queue else {
+        unreachable!()
+    };
+    assert_eq!(items, &[1, 2, 3, 4]);
+    let mut queue = Queue::Closed { items: vec![1] };
+    queue.clear_items();
+    let Queue::Closed { items } = queue else {
+        unreachable!()
+    };
+    assert!(items.is_empty());
 }
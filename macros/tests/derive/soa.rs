@@ -0,0 +1,159 @@
+use std_reset::prelude::{Soa, To};
+
+#[test]
+fn named_structure() {
+    #[derive(Soa, Debug, Clone, PartialEq)]
+    #[soa(derive(Debug, Clone, PartialEq))]
+    struct Particle {
+        x: f32,
+        mass: u32,
+        name: String,
+    }
+
+    let particles = vec![
+        Particle {
+            x: 0.0,
+            mass: 1,
+            name: "a".to_string(),
+        },
+        Particle {
+            x: 2.0,
+            mass: 3,
+            name: "b".to_string(),
+        },
+        Particle {
+            x: 4.0,
+            mass: 5,
+            name: "c".to_string(),
+        },
+    ];
+
+    let mut soa = particles.clone().to::<ParticleVec>();
+    assert_eq!(soa.len(), 3);
+    assert!(!soa.is_empty());
+    assert_eq!(soa.x(), [0.0, 2.0, 4.0]);
+    assert_eq!(soa.mass(), [1, 3, 5]);
+
+    let second = soa.get(1).unwrap();
+    assert_eq!(
+        (*second.x, *second.mass, second.name.as_str()),
+        (2.0, 3, "b")
+    );
+    assert!(soa.get(3).is_none());
+
+    let names: Vec<_> = soa.iter().map(|particle| particle.name.as_str()).collect();
+    assert_eq!(names, ["a", "b", "c"]);
+
+    assert_eq!(soa.swap_remove(0), particles[0]);
+    assert_eq!(soa.mass(), [5, 3]);
+
+    let back = soa.clone().to::<Vec<Particle>>();
+    assert_eq!(back, [particles[2].clone(), particles[1].clone()]);
+
+    let mut empty = ParticleVec::new();
+    assert!(empty.is_empty());
+    empty.push(particles[0].clone());
+    assert_eq!(empty.to::<Vec<Particle>>(), [particles[0].clone()]);
+}
+
+#[test]
+fn unnamed_structure() {
+    #[derive(Soa, Debug, PartialEq)]
+    struct Sample(u8, &'static str);
+
+    let mut soa = SampleVec::with_capacity(2);
+    soa.push(Sample(1, "one"));
+    soa.push(Sample(2, "two"));
+    assert_eq!(soa.column_0(), [1, 2]);
+    assert_eq!(soa.column_1(), ["one", "two"]);
+
+    let first = soa.get(0).unwrap();
+    let copy = first;
+    assert_eq!((*copy.0, *first.1), (1, "one"));
+
+    assert_eq!(
+        soa.to::<Vec<Sample>>(),
+        [Sample(1, "one"), Sample(2, "two")]
+    );
+}
+
+#[test]
+fn generic_structure() {
+    #[derive(Soa, Debug, PartialEq)]
+    struct Entry<'a, T, const N: usize>
+    where
+        T: Copy,
+    {
+        key: &'a str,
+        values: [T; N],
+    }
+
+    let soa = vec![
+        Entry {
+            key: "a",
+            values: [1, 2],
+        },
+        Entry {
+            key: "b",
+            values: [3, 4],
+        },
+    ]
+    .to::<EntryVec<i32, 2>>();
+    let sums: Vec<i32> = soa.iter().map(|entry| entry.values.iter().sum()).collect();
+    assert_eq!(sums, [3, 7]);
+    assert_eq!(*soa.get(1).unwrap().key, "b");
+}
+
+#[test]
+fn columns() {
+    #[derive(Soa, Debug, Clone, PartialEq)]
+    struct Point {
+        x: i32,
+        #[std_reset(rename = "ys", vis = "pub")]
+        y: i32,
+        #[std_reset(rename = "length")]
+        len: u8,
+    }
+
+    let mut soa = PointVec::new();
+    soa.push(Point { x: 1, y: 2, len: 3 });
+    soa.push(Point { x: 4, y: 5, len: 6 });
+
+    soa.x_mut()[1] = 7;
+    soa.ys_mut().iter_mut().for_each(|y| *y *= 10);
+    assert_eq!(soa.x(), [1, 7]);
+    assert_eq!(soa.ys(), [20, 50]);
+    assert_eq!(soa.length(), [3, 6]);
+
+    // через срезы длину столбца изменить нельзя, поэтому элементы не теряются
+    assert_eq!(soa.len(), 2);
+    let second = soa.get(1).unwrap();
+    assert_eq!((*second.x, *second.y, *second.len), (7, 50, 6));
+    assert_eq!(
+        soa.to::<Vec<Point>>(),
+        [
+            Point {
+                x: 1,
+                y: 20,
+                len: 3
+            },
+            Point {
+                x: 7,
+                y: 50,
+                len: 6
+            }
+        ]
+    );
+}
+
+#[test]
+fn soa_lifetime() {
+    #[derive(Soa, Debug, PartialEq)]
+    struct Borrowed<'soa> {
+        text: &'soa str,
+    }
+
+    let soa = vec![Borrowed { text: "a" }, Borrowed { text: "b" }].to::<BorrowedVec>();
+    assert_eq!(soa.text(), ["a", "b"]);
+    assert_eq!(*soa.get(1).unwrap().text, "b");
+}
@@ -0,0 +1,172 @@
+use std_reset::prelude::{
+    Accessors, Default, Deref, Display, EnumUtils, Fields, Getter, Lens, New, Patch, Preset, Reset,
+    Setter,
+};
+
+#[test]
+fn setter_and_getter() {
+    #[derive(Setter, Getter, Default, Debug)]
+    struct User {
+        #[std_reset(rename = "login", into)]
+        name: String,
+        #[std_reset(skip)]
+        password: String,
+        #[exclude_getter]
+        #[std_reset(vis = "pub(crate)")]
+        age: u8,
+        #[setter(into, name = "with_email")]
+        email: String,
+    }
+
+    let user = User::default()
+        .set_login("ferris")
+        .set_age(8)
+        .with_email("ferris@example.com");
+    assert_eq!(user.get_login(), "ferris");
+    assert_eq!(user.age, 8);
+    assert_eq!(user.get_email(), "ferris@example.com");
+    assert!(user.password.is_empty());
+
+    #[derive(Accessors, Default)]
+    #[setter(mode = "plain")]
+    struct Point(#[std_reset(rename = "x")] i32, #[getter(copy)] i32);
+
+    let mut point = Point::default();
+    point.set_x(1);
+    point.set_1(2);
+    assert_eq!((*point.get_x(), point.get_1()), (1, 2));
+}
+
+#[test]
+fn new_and_default() {
+    #[derive(New, Default, Reset, Debug, PartialEq)]
+    struct Connection {
+        #[std_reset(into, rename = "address")]
+        host: String,
+        #[std_reset(skip, default = 3)]
+        retries: u8,
+        #[std_reset(skip)]
+        #[default(vec!["created".to_string()])]
+        log: Vec<String>,
+    }
+
+    let mut connection = Connection::new("localhost");
+    assert_eq!(
+        connection,
+        Connection {
+            host: "localhost".to_string(),
+            retries: 3,
+            log: vec!["created".to_string()],
+        }
+    );
+    assert_eq!(Connection::default().retries, 3);
+
+    connection.retries = 0;
+    connection.log.clear();
+    connection.reset_address();
+    assert!(connection.host.is_empty());
+    connection.reset();
+    assert_eq!(connection.retries, 0);
+    assert!(connection.log.is_empty());
+
+    #[derive(New, Debug, PartialEq)]
+    struct Pair<T>(#[std_reset(into)] String, #[std_reset(skip)] Option<T>);
+
+    assert_eq!(Pair::<u8>::new("a"), Pair("a".to_string(), None));
+}
+
+#[test]
+fn patch_fields_and_lens() {
+    #[derive(Patch, Fields, Lens, Clone, Debug, PartialEq)]
+    #[patch(derive(Debug, PartialEq))]
+    struct State {
+        #[std_reset(rename = "title")]
+        name: String,
+        #[std_reset(skip)]
+        cache: Vec<u8>,
+        count: u32,
+    }
+
+    let old = State {
+        name: "a".to_string(),
+        cache: vec![1],
+        count: 1,
+    };
+    let new = State {
+        name: "a".to_string(),
+        cache: vec![2],
+        count: 2,
+    };
+    assert_eq!(
+        old.diff(&new),
+        StatePatch {
+            name: None,
+            count: Some(2)
+        }
+    );
+
+    assert_eq!(State::FIELD_NAMES, ["title", "count"]);
+    assert_eq!(StateField::from_name("title"), Some(StateField::Title));
+    assert!(old.get_dyn("cache").is_none());
+
+    let state = State::lens_title().set(old, "b".to_string());
+    assert_eq!(state.name, "b");
+}
+
+#[test]
+fn enum_utils_and_others() {
+    #[derive(EnumUtils, Debug, PartialEq, Clone, Copy)]
+    enum Level {
+        #[std_reset(rename = "low")]
+        Low,
+        #[enum_utils(rename = "mid")]
+        #[std_reset(rename = "medium")]
+        Medium,
+    }
+
+    assert_eq!(Level::Low.name(), "low");
+    assert_eq!(Level::Medium.name(), "mid");
+    assert_eq!("low".parse::<Level>(), Ok(Level::Low));
+
+    // `vis` не относится ни к `Deref`, ни к `Display`
+    #[derive(Deref, Display)]
+    #[display("{0}")]
+    struct Name(#[std_reset(vis = "pub")] String);
+
+    let name = Name("ferris".to_string());
+    assert_eq!(name.len(), 6);
+    assert_eq!(name.to_string(), "ferris");
+}
+
+#[test]
+fn display_and_preset() {
+    #[derive(Display, Debug)]
+    struct Session {
+        #[std_reset(rename = "user")]
+        login: &'static str,
+        #[std_reset(skip)]
+        _token: &'static str,
+    }
+
+    let session = Session {
+        login: "ferris",
+        _token: "secret",
+    };
+    assert_eq!(session.to_string(), r#"Session { user: "ferris", .. }"#);
+
+    #[derive(Preset)]
+    #[preset(name = "admin", role = "admin", level = 9)]
+    struct Account {
+        #[std_reset(into, rename = "role")]
+        kind: String,
+        #[std_reset(default = 1)]
+        level: u8,
+        #[std_reset(skip)]
+        history: Vec<String>,
+    }
+
+    let admin = Account::admin();
+    assert_eq!(admin.kind, "admin");
+    assert_eq!(admin.level, 9);
+    assert!(admin.history.is_empty());
+}
@@ -5,8 +5,12 @@ pub mod traits;
 pub mod prelude {
     pub use super::traits::{
         as_prim::AsPrim,
+        fields::{Visitor, VisitorMut},
+        lens::Lens,
         of_to::{Of, To},
         try_to::TryTo,
     };
-    pub use std_reset_macros::{Default, Deref, Display, Getter, New, Setter};
+    pub use std_reset_macros::{
+        Accessors, AsPrim, Default, Deref, Display, EnumUtils, Error, Fields, Getter, Lens, New, Newtype, Ops, Patch, Preset, Reset, Setter, Soa,
+    };
 }
@@ -38,6 +38,50 @@
 //! # use std_reset::traits::as_prim::ToPrim;
 //! let num = 2.to_f32();
 //! ```
+//!
+//! # Операторы со смешанными типами
+//!
+//! Макрос [`Ops`](crate::prelude::Ops) с атрибутом `#[ops(as_prim)]` реализует операторы,
+//! в которых левый операнд преобразуется через [`AsPrim`] к типу правого:
+//! ```
+//! use std_reset::prelude::Ops;
+//!
+//! #[derive(Ops, Debug, PartialEq)]
+//! #[ops(as_prim)]
+//! struct Vector<T>([T; 2]);
+//!
+//! assert_eq!(Vector([1.5, 2.2]) + Vector([2, 2]), Vector([3, 4]));
+//! assert_eq!(Vector([2, 2]) + Vector([1.5, 2.5]), Vector([3.5, 4.5]));
+//! assert_eq!(Vector([1, 2]) * 0.5, Vector([0.5, 1.0]));
+//!
+//! let mut vector = Vector([1_u8, 2]);
+//! vector += Vector([1.9, 1.9]);
+//! assert_eq!(vector, Vector([2, 3]));
+//! ```
+//!
+//! # Перечисления
+//! Макрос [`AsPrim`](crate::prelude::AsPrim) для перечисления с `#[repr(...)]` создаёт метод `as_`,
+//! который приводит дискриминант варианта через [`FromPrim`]. Сами трейты модуля для перечисления
+//! не реализуются, поэтому в обобщённом коде с ограничением `T: AsPrim` используется результат `as_`.
+//! Обратное преобразование реализовано через [`TryFrom`] и завершается ошибкой для неизвестного дискриминанта:
+//! ```
+//! use std_reset::prelude::{AsPrim, To, TryTo};
+//!
+//! #[derive(AsPrim, Debug, PartialEq)]
+//! #[repr(u16)]
+//! enum Status {
+//!     Ok = 200,
+//!     NotFound = 404,
+//! }
+//!
+//! assert_eq!(Status::Ok.as_::<f64>(), 200.0);
+//! assert_eq!(Status::NotFound.to::<u16>(), 404);
+//! assert_eq!(404_u16.try_to::<Status>(), Ok(Status::NotFound));
+//! assert_eq!(
+//!     1_u16.try_to::<Status>().unwrap_err().to_string(),
+//!     "unknown discriminant `1` of `Status`"
+//! );
+//! ```
 
 use paste::paste;
 
@@ -0,0 +1,53 @@
+//! Обход полей структуры, реализующей [`Fields`](crate::prelude::Fields).
+//!
+//! Посетитель получает имя и значение каждого поля в порядке объявления.
+//! Трейты реализованы для замыканий с подходящей сигнатурой.
+//!
+//! ## Пример
+//! ```
+//! use std::any::Any;
+//! use std_reset::prelude::Fields;
+//!
+//! #[derive(Fields)]
+//! struct User {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let mut user = User { name: "Ferris".to_string(), age: 8 };
+//!
+//! let mut names = vec![];
+//! user.visit(&mut |name: &'static str, _: &dyn Any| names.push(name));
+//! assert_eq!(names, User::FIELD_NAMES);
+//!
+//! user.visit_mut(&mut |_: &'static str, value: &mut dyn Any| {
+//!     if let Some(age) = value.downcast_mut::<u32>() {
+//!         *age += 1;
+//!     }
+//! });
+//! assert_eq!(user.age, 9);
+//! ```
+
+use std::any::Any;
+
+/// Посетитель, получающий ссылки на поля.
+pub trait Visitor {
+    fn visit(&mut self, name: &'static str, value: &dyn Any);
+}
+
+impl<F: FnMut(&'static str, &dyn Any)> Visitor for F {
+    fn visit(&mut self, name: &'static str, value: &dyn Any) {
+        self(name, value)
+    }
+}
+
+/// Посетитель, получающий изменяемые ссылки на поля.
+pub trait VisitorMut {
+    fn visit_mut(&mut self, name: &'static str, value: &mut dyn Any);
+}
+
+impl<F: FnMut(&'static str, &mut dyn Any)> VisitorMut for F {
+    fn visit_mut(&mut self, name: &'static str, value: &mut dyn Any) {
+        self(name, value)
+    }
+}
@@ -0,0 +1,145 @@
+//! Линзы - значения, которые указывают на поле структуры и позволяют читать и изменять его.
+//!
+//! Линзы полей создаются макросом [`Lens`](crate::prelude::Lens) и соединяются через
+//! [`then`](Lens::then), поэтому вложенное поле изменяется без ручной распаковки каждого уровня.
+//!
+//! ## Пример
+//! ```
+//! use std_reset::prelude::Lens;
+//!
+//! #[derive(Lens, Clone, Debug, PartialEq)]
+//! struct Address {
+//!     city: String,
+//! }
+//!
+//! #[derive(Lens, Clone, Debug, PartialEq)]
+//! struct User {
+//!     name: String,
+//!     address: Address,
+//! }
+//!
+//! let user = User {
+//!     name: "Ferris".to_string(),
+//!     address: Address { city: "Berlin".to_string() },
+//! };
+//!
+//! let city = User::lens_address().then(Address::lens_city());
+//! assert_eq!(city.get(&user), "Berlin");
+//!
+//! let user = city.set(user, "Paris".to_string());
+//! assert_eq!(user.address.city, "Paris");
+//!
+//! let user = city.modify(user, |city| city.to_uppercase());
+//! assert_eq!(user.address.city, "PARIS");
+//! ```
+
+use std::marker::PhantomData;
+
+/// Линза из значения `S` на его часть `A`.
+pub trait Lens<S, A> {
+    /// Ссылка на часть `source`.
+    fn get<'a>(&self, source: &'a S) -> &'a A
+    where
+        Self: 'a;
+
+    /// Изменяемая ссылка на часть `source`.
+    fn get_mut<'a>(&self, source: &'a mut S) -> &'a mut A
+    where
+        Self: 'a;
+
+    /// Возвращает `source`, в котором часть заменена на `value`.
+    fn set(&self, mut source: S, value: A) -> S {
+        *self.get_mut(&mut source) = value;
+        source
+    }
+
+    /// Возвращает `source`, в котором часть заменена на результат `f`.
+    fn modify(&self, mut source: S, f: impl FnOnce(&A) -> A) -> S {
+        let part = self.get_mut(&mut source);
+        *part = f(part);
+        source
+    }
+
+    /// Линза на часть `B` внутри `A`.
+    fn then<B, L: Lens<A, B>>(self, next: L) -> Then<Self, L, A>
+    where
+        Self: Sized,
+    {
+        Then {
+            first: self,
+            second: next,
+            part: PhantomData,
+        }
+    }
+}
+
+/// Линза на поле, заданная функциями доступа.
+pub struct FieldLens<S, A> {
+    get: fn(&S) -> &A,
+    get_mut: fn(&mut S) -> &mut A,
+}
+
+impl<S, A> FieldLens<S, A> {
+    pub const fn new(get: fn(&S) -> &A, get_mut: fn(&mut S) -> &mut A) -> Self {
+        Self { get, get_mut }
+    }
+}
+
+impl<S, A> Clone for FieldLens<S, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, A> Copy for FieldLens<S, A> {}
+
+impl<S, A> Lens<S, A> for FieldLens<S, A> {
+    fn get<'a>(&self, source: &'a S) -> &'a A
+    where
+        Self: 'a,
+    {
+        (self.get)(source)
+    }
+
+    fn get_mut<'a>(&self, source: &'a mut S) -> &'a mut A
+    where
+        Self: 'a,
+    {
+        (self.get_mut)(source)
+    }
+}
+
+/// Композиция линз, созданная [`Lens::then`].
+pub struct Then<L1, L2, A> {
+    first: L1,
+    second: L2,
+    part: PhantomData<fn() -> A>,
+}
+
+impl<L1: Clone, L2: Clone, A> Clone for Then<L1, L2, A> {
+    fn clone(&self) -> Self {
+        Self {
+            first: self.first.clone(),
+            second: self.second.clone(),
+            part: PhantomData,
+        }
+    }
+}
+
+impl<L1: Copy, L2: Copy, A> Copy for Then<L1, L2, A> {}
+
+impl<S, A, B, L1: Lens<S, A>, L2: Lens<A, B>> Lens<S, B> for Then<L1, L2, A> {
+    fn get<'a>(&self, source: &'a S) -> &'a B
+    where
+        Self: 'a,
+    {
+        self.second.get(self.first.get(source))
+    }
+
+    fn get_mut<'a>(&self, source: &'a mut S) -> &'a mut B
+    where
+        Self: 'a,
+    {
+        self.second.get_mut(self.first.get_mut(source))
+    }
+}
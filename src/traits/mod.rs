@@ -1,3 +1,5 @@
 pub mod as_prim;
+pub mod fields;
+pub mod lens;
 pub mod of_to;
 pub mod try_to;
\ No newline at end of file
@@ -9,7 +9,7 @@
 //! В стандартной реализации [`From`]/[`Into`]  это можно сделать с помощью следующих конструкций:
 //! - `<TypeFrom as Into<TypeInto>>::`
 //! - `Into::<TypeInto>>::`
-//! , где `TypeFrom` - тип, который укзан в реализации как `impl From<TypeFrom> for TypeInto`.
+//!   , где `TypeFrom` - тип, который укзан в реализации как `impl From<TypeFrom> for TypeInto`.
 //!
 //! ## Пример
 //! ```
@@ -98,6 +98,6 @@ where
     I: Of<F>,
 {
     fn of(vec: &Vec<F>) -> Self {
-        vec.into_iter().cloned().map(I::of).collect()
+        vec.iter().cloned().map(I::of).collect()
     }
 }
\ No newline at end of file